@@ -0,0 +1,121 @@
+use crate::ai::AiPlayer;
+use crate::models::*;
+
+/// A simple rule-based player: it plays its most expensive affordable cards,
+/// attacks when a trade favors it and springs a feign when an attack would hurt.
+/// It only looks at information its player could see.
+#[derive(Debug, Clone, Default)]
+pub struct GreedyAi;
+
+impl GreedyAi {
+    pub fn new() -> Self {
+        GreedyAi
+    }
+
+    /// Decide whether to answer an attack in `column` with a face-down feign
+    fn respond(&self, me: &Player, opponent: &Player, column: usize) -> PlayerAction {
+        let threatened = match (&opponent.board.creatures[column], &me.board.creatures[column]) {
+            (Some(attacker), Some(blocker)) => attacker.current_attack >= blocker.current_defense,
+            (Some(attacker), None) => attacker.current_attack > 0,
+            (None, _) => false,
+        };
+        if !threatened {
+            return PlayerAction::PassResponse;
+        }
+
+        // Prefer the feign guarding the attacked lane
+        let feign_column = std::iter::once(column)
+            .chain(0..BOARD_COLUMNS)
+            .find(|&c| me.board.feigns[c].as_ref().is_some_and(|feign| !feign.is_revealed));
+        match feign_column {
+            Some(column) => PlayerAction::RevealFeign { column },
+            None => PlayerAction::PassResponse,
+        }
+    }
+
+    /// Pick the next card to put on the board, if any is worth playing
+    fn place(&self, state: &GameState, me: &Player, opponent: &Player) -> PlayerAction {
+        let mut affordable: Vec<&CardInstance> = me.hand.iter().filter(|card| card.mana_cost <= me.mana).collect();
+        affordable.sort_by_key(|card| std::cmp::Reverse(card.mana_cost));
+
+        // Creatures first, blocking an unopposed enemy creature when possible
+        if let Some(card) = affordable.iter().find(|card| card.card_type == CardType::Creature) {
+            let open = |c: &usize| me.board.creatures[*c].is_none();
+            let column = (0..BOARD_COLUMNS)
+                .filter(open)
+                .find(|&c| opponent.board.creatures[c].is_some())
+                .or_else(|| (0..BOARD_COLUMNS).find(open));
+            if let Some(column) = column {
+                return PlayerAction::PlayCreature { instance_id: card.instance_id, column };
+            }
+        }
+
+        // Only start a global effect when we hold the board
+        let my_creatures = me.board.creatures.iter().flatten().count();
+        let their_creatures = opponent.board.creatures.iter().flatten().count();
+        if state.global_effect.is_none() && my_creatures > 0 && my_creatures >= their_creatures {
+            if let Some(card) = affordable.iter().find(|card| card.card_type == CardType::Effect) {
+                return PlayerAction::PlayEffect { instance_id: card.instance_id };
+            }
+        }
+
+        // Hide feigns behind our own creatures first
+        if let Some(card) = affordable.iter().find(|card| card.card_type == CardType::Feign) {
+            let open = |c: &usize| me.board.feigns[*c].is_none();
+            let column = (0..BOARD_COLUMNS)
+                .filter(open)
+                .find(|&c| me.board.creatures[c].is_some())
+                .or_else(|| (0..BOARD_COLUMNS).find(open));
+            if let Some(column) = column {
+                return PlayerAction::PlayFeign { instance_id: card.instance_id, column };
+            }
+        }
+
+        PlayerAction::EndPhase
+    }
+
+    /// Pick an attack that hits face or wins its trade, if any
+    fn attack(&self, me: &Player, opponent: &Player) -> PlayerAction {
+        for column in 0..BOARD_COLUMNS {
+            let Some(attacker) = me.board.creatures[column].as_ref().filter(|creature| !creature.is_tapped) else {
+                continue;
+            };
+            let worth_it = match &opponent.board.creatures[column] {
+                None => attacker.current_attack > 0,
+                Some(blocker) => {
+                    let kills = attacker.current_attack >= blocker.current_defense;
+                    let survives = blocker.current_attack < attacker.current_defense;
+                    kills && (survives || attacker.card.mana_cost <= blocker.card.mana_cost)
+                }
+            };
+            if worth_it {
+                return PlayerAction::Attack { column };
+            }
+        }
+        PlayerAction::EndPhase
+    }
+}
+
+impl AiPlayer for GreedyAi {
+    fn choose_action(&mut self, state: &GameState, player_id: u32) -> Option<PlayerAction> {
+        if state.acting_player() != player_id {
+            return None;
+        }
+
+        let (me, opponent) = if player_id == 1 {
+            (&state.player1, &state.player2)
+        } else {
+            (&state.player2, &state.player1)
+        };
+
+        if let Some(pending) = &state.pending_attack {
+            return Some(self.respond(me, opponent, pending.column));
+        }
+
+        Some(match state.phase {
+            GamePhase::Placement => self.place(state, me, opponent),
+            GamePhase::Attack => self.attack(me, opponent),
+            GamePhase::Draw | GamePhase::EndTurn => PlayerAction::EndPhase,
+        })
+    }
+}
@@ -0,0 +1,324 @@
+use crate::ai::{AiPlayer, GreedyAi};
+use crate::core::GameEngine;
+use crate::models::*;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Search budget and tuning for `MctsAi`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MctsConfig {
+    pub max_iterations: u32,
+    pub time_budget_ms: u64,
+    pub exploration: f64,       // UCB1 exploration constant
+    pub playout_depth: usize,   // Actions per playout before the position is scored
+    pub playout_randomness: f64, // Chance a playout move is random instead of greedy
+}
+
+impl Default for MctsConfig {
+    fn default() -> Self {
+        MctsConfig {
+            max_iterations: 2000,
+            time_budget_ms: 300,
+            exploration: 0.7,
+            playout_depth: 60,
+            playout_randomness: 0.2,
+        }
+    }
+}
+
+/// A node of the search tree; nodes are shared by every determinization in
+/// which their action was legal
+struct Node {
+    action: Option<PlayerAction>,
+    player: u32, // Who took `action` to reach this node
+    parent: Option<usize>,
+    children: Vec<usize>,
+    visits: u32,
+    availability: u32, // Iterations in which this node could have been chosen
+    total_reward: f64, // Summed from `player`'s point of view
+}
+
+/// Information set Monte Carlo tree search. Every iteration samples a
+/// determinization - a concrete guess at the hidden cards consistent with what
+/// the AI's player can see - and plays it out on a cloned engine, so the
+/// statistics average over everything the opponent might be holding.
+pub struct MctsAi {
+    config: MctsConfig,
+    rng: StdRng,
+}
+
+impl MctsAi {
+    pub fn new(config: MctsConfig) -> Self {
+        MctsAi { config, rng: StdRng::from_entropy() }
+    }
+
+    /// Create a search whose sampling is reproducible
+    pub fn with_seed(config: MctsConfig, seed: u64) -> Self {
+        MctsAi { config, rng: StdRng::seed_from_u64(seed) }
+    }
+
+    /// Run the search from `state` and return the most visited action
+    fn search(&mut self, state: &GameState, player_id: u32, legal: Vec<PlayerAction>) -> PlayerAction {
+        let mut tree = vec![Node {
+            action: None,
+            player: 0,
+            parent: None,
+            children: Vec::new(),
+            visits: 0,
+            availability: 0,
+            total_reward: 0.0,
+        }];
+
+        let deadline = Instant::now() + Duration::from_millis(self.config.time_budget_ms);
+        let mut iterations = 0;
+        while iterations < self.config.max_iterations && Instant::now() < deadline {
+            let mut engine = GameEngine::from_state(determinize(state, player_id, &mut self.rng));
+            engine.disable_undo();
+            let leaf = self.select_and_expand(&mut tree, &mut engine);
+            let rewards = self.playout(&mut engine);
+            backpropagate(&mut tree, leaf, rewards);
+            iterations += 1;
+        }
+
+        tree[0].children.iter()
+            .max_by_key(|&&child| tree[child].visits)
+            .and_then(|&child| tree[child].action.clone())
+            .filter(|action| legal.contains(action))
+            .unwrap_or_else(|| legal[0].clone())
+    }
+
+    /// Walk down the tree along actions legal in this determinization, applying
+    /// them to `engine`, and add one untried action as a new leaf
+    fn select_and_expand(&mut self, tree: &mut Vec<Node>, engine: &mut GameEngine) -> usize {
+        let mut current = 0;
+        loop {
+            if engine.is_game_over().is_some() {
+                return current;
+            }
+            let player = engine.state.acting_player();
+            let legal = engine.legal_actions(player);
+
+            let available: Vec<usize> = tree[current].children.iter()
+                .copied()
+                .filter(|&child| tree[child].action.as_ref().is_some_and(|action| legal.contains(action)))
+                .collect();
+            for &child in &available {
+                tree[child].availability += 1;
+            }
+
+            let untried: Vec<&PlayerAction> = legal.iter()
+                .filter(|action| !available.iter().any(|&child| tree[child].action.as_ref() == Some(action)))
+                .collect();
+            if let Some(&action) = untried.choose(&mut self.rng) {
+                let child = tree.len();
+                tree.push(Node {
+                    action: Some(action.clone()),
+                    player,
+                    parent: Some(current),
+                    children: Vec::new(),
+                    visits: 0,
+                    availability: 1,
+                    total_reward: 0.0,
+                });
+                tree[current].children.push(child);
+                let _ = engine.process_action(player, action.clone());
+                return child;
+            }
+
+            let exploration = self.config.exploration;
+            let ucb = |node: &Node| {
+                node.total_reward / node.visits as f64
+                    + exploration * ((node.availability as f64).ln() / node.visits as f64).sqrt()
+            };
+            let Some(&next) = available.iter().max_by(|&&a, &&b| ucb(&tree[a]).total_cmp(&ucb(&tree[b]))) else {
+                return current;
+            };
+            if let Some(action) = tree[next].action.clone() {
+                let _ = engine.process_action(player, action);
+            }
+            current = next;
+        }
+    }
+
+    /// Finish the game with a mostly greedy policy, returning each player's
+    /// reward in [0, 1]
+    fn playout(&mut self, engine: &mut GameEngine) -> [f64; 2] {
+        let mut greedy = GreedyAi::new();
+        for _ in 0..self.config.playout_depth {
+            if engine.is_game_over().is_some() {
+                break;
+            }
+            let player = engine.state.acting_player();
+            let action = if self.rng.gen_bool(self.config.playout_randomness) {
+                engine.legal_actions(player).choose(&mut self.rng).cloned()
+            } else {
+                greedy.choose_action(&engine.state, player)
+            };
+            let action = action.unwrap_or(PlayerAction::EndPhase);
+            if engine.process_action(player, action).is_err() {
+                let fallback = if engine.state.pending_attack.is_some() {
+                    PlayerAction::PassResponse
+                } else {
+                    PlayerAction::EndPhase
+                };
+                let _ = engine.process_action(player, fallback);
+            }
+        }
+
+        let player1_reward = match engine.is_game_over() {
+            Some(1) => 1.0,
+            Some(_) => 0.0,
+            None => evaluate(&engine.state),
+        };
+        [player1_reward, 1.0 - player1_reward]
+    }
+}
+
+impl AiPlayer for MctsAi {
+    fn choose_action(&mut self, state: &GameState, player_id: u32) -> Option<PlayerAction> {
+        if state.acting_player() != player_id {
+            return None;
+        }
+
+        let engine = GameEngine::from_state(state.clone());
+        let legal = engine.legal_actions(player_id);
+        if legal.len() <= 1 {
+            return legal.into_iter().next();
+        }
+        Some(self.search(state, player_id, legal))
+    }
+}
+
+/// Add a playout's rewards to every node on the path back to the root
+fn backpropagate(tree: &mut [Node], leaf: usize, rewards: [f64; 2]) {
+    let mut current = Some(leaf);
+    while let Some(index) = current {
+        let node = &mut tree[index];
+        node.visits += 1;
+        if node.player != 0 {
+            node.total_reward += rewards[node.player as usize - 1];
+        }
+        current = node.parent;
+    }
+}
+
+/// Score an unfinished game for player 1 in [0, 1] from life totals and board strength
+fn evaluate(state: &GameState) -> f64 {
+    let strength = |player: &Player| {
+        let board: u32 = player.board.creatures.iter().flatten()
+            .map(|creature| creature.current_attack + creature.current_defense)
+            .sum();
+        player.life as f64 + player.shield as f64 + board as f64 * 0.5
+    };
+    let advantage = strength(&state.player1) - strength(&state.player2);
+    1.0 / (1.0 + (-advantage / 10.0).exp())
+}
+
+/// Sample a full game state consistent with what `player_id` can see: the
+/// opponent's hand and face-down feigns are redealt from the cards they could
+/// be, and both decks are reshuffled
+fn determinize(state: &GameState, player_id: u32, rng: &mut StdRng) -> GameState {
+    let mut sample = state.clone();
+    sample.events.clear(); // Playouts never read the events; don't pay to clone them again
+    sample.seed = rng.gen(); // The real seed would give away the real deck order
+
+    let (me, opponent) = if player_id == 1 {
+        (&mut sample.player1, &mut sample.player2)
+    } else {
+        (&mut sample.player2, &mut sample.player1)
+    };
+    me.deck.shuffle(rng);
+
+    // Everything of the opponent's we cannot see goes back into one pool
+    let hand_size = opponent.hand.len();
+    let mut pool: Vec<CardInstance> = opponent.hand.drain(..).chain(opponent.deck.drain(..)).collect();
+    let hidden_columns: Vec<usize> = (0..BOARD_COLUMNS)
+        .filter(|&c| opponent.board.feigns[c].as_ref().is_some_and(|feign| !feign.is_revealed))
+        .collect();
+    for &column in &hidden_columns {
+        if let Some(feign) = opponent.board.feigns[column].take() {
+            pool.push(feign.card);
+        }
+    }
+    pool.shuffle(rng);
+
+    // Face-down slots can only hold feigns
+    for &column in &hidden_columns {
+        let index = pool.iter().position(|card| card.card_type == CardType::Feign)
+            .expect("the pool holds at least the feigns that were face-down");
+        opponent.board.feigns[column] = Some(FeignCard { card: pool.swap_remove(index), is_revealed: false });
+    }
+
+    opponent.hand = pool.split_off(pool.len() - hand_size);
+    opponent.deck = pool;
+    sample
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::game_engine::tests::{bare_game, new_game, place_creature, place_feign, play_random};
+
+    fn sorted_ids<'a>(cards: impl IntoIterator<Item = &'a CardInstance>) -> Vec<u32> {
+        let mut ids: Vec<u32> = cards.into_iter().map(|card| card.instance_id).collect();
+        ids.sort();
+        ids
+    }
+
+    /// The opponent's cards player 1 can't see: their hand, deck and face-down feigns
+    fn hidden_cards(player: &Player) -> Vec<u32> {
+        let feigns = player.board.feigns.iter().flatten().filter(|feign| !feign.is_revealed).map(|feign| &feign.card);
+        sorted_ids(player.hand.iter().chain(&player.deck).chain(feigns))
+    }
+
+    #[test]
+    fn determinizations_only_redeal_hidden_cards() {
+        let mut engine = new_game(8);
+        play_random(&mut engine, 8, 30);
+        place_feign(&mut engine, 2, 18, 0); // Soul Drain
+        place_feign(&mut engine, 2, 14, 1); // Shield Trap
+        engine.state.player2.board.feigns[1].as_mut().unwrap().is_revealed = true;
+        let state = &engine.state;
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let mut hands = Vec::new();
+        for _ in 0..10 {
+            let sample = determinize(state, 1, &mut rng);
+            assert_ne!(sample.seed, state.seed);
+
+            // Player 1's own cards are known, but their deck order isn't
+            let ids = |cards: &[CardInstance]| cards.iter().map(|card| card.instance_id).collect::<Vec<_>>();
+            assert_eq!(ids(&sample.player1.hand), ids(&state.player1.hand));
+            assert_eq!(sorted_ids(&sample.player1.deck), sorted_ids(&state.player1.deck));
+
+            // The opponent's hidden cards are dealt out again in the same numbers
+            let (real, guess) = (&state.player2, &sample.player2);
+            assert_eq!(hidden_cards(guess), hidden_cards(real));
+            assert_eq!((guess.hand.len(), guess.deck.len()), (real.hand.len(), real.deck.len()));
+            let face_down = guess.board.feigns[0].as_ref().unwrap();
+            assert!(!face_down.is_revealed && face_down.card.card_type == CardType::Feign);
+            let revealed = |player: &Player| player.board.feigns[1].as_ref().unwrap().card.instance_id;
+            assert_eq!(revealed(guess), revealed(real));
+            let creatures = |player: &Player| serde_json::to_value(&player.board.creatures).unwrap();
+            assert_eq!(creatures(guess), creatures(real));
+            hands.push(ids(&guess.hand));
+        }
+        hands.dedup();
+        assert!(hands.len() > 1, "every determinization dealt the same hand");
+    }
+
+    #[test]
+    fn search_finds_a_lethal_attack() {
+        let mut engine = bare_game(GamePhase::Attack);
+        place_creature(&mut engine, 1, 5, 0); // Flame Dragon, 7/5
+        engine.state.player2.life = 5;
+        let config = MctsConfig { max_iterations: 200, time_budget_ms: 10_000, ..MctsConfig::default() };
+
+        let action = MctsAi::with_seed(config, 3).choose_action(&engine.state, 1);
+
+        assert_eq!(action, Some(PlayerAction::Attack { column: 0 }));
+    }
+}
@@ -0,0 +1,72 @@
+pub mod greedy;
+pub mod mcts;
+
+pub use greedy::GreedyAi;
+pub use mcts::{MctsAi, MctsConfig};
+
+use crate::core::GameEngine;
+use crate::models::*;
+use serde::{Deserialize, Serialize};
+
+/// Upper bound on actions an AI may take in one turn, so a
+/// misbehaving AI can never hang the game
+pub const MAX_ACTIONS_PER_TURN: usize = 200;
+
+/// A computer-controlled player
+pub trait AiPlayer {
+    /// Choose the next action for `player_id`, or None if it is not their move
+    fn choose_action(&mut self, state: &GameState, player_id: u32) -> Option<PlayerAction>;
+}
+
+/// The built-in computer opponents
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AiKind {
+    #[default]
+    Greedy,
+    Mcts, // Tree search with the default budget
+}
+
+impl AiKind {
+    /// Create a fresh AI of this kind
+    pub fn create(self) -> Box<dyn AiPlayer + Send> {
+        match self {
+            AiKind::Greedy => Box::new(GreedyAi::new()),
+            AiKind::Mcts => Box::new(MctsAi::new(MctsConfig::default())),
+        }
+    }
+}
+
+/// Let an AI act for `player_id` until the game needs someone else's input
+/// (or ends), returning the result of every action it took
+pub fn play_turn(engine: &mut GameEngine, ai: &mut dyn AiPlayer, player_id: u32) -> Vec<ActionResult> {
+    let mut results = Vec::new();
+
+    while results.len() < MAX_ACTIONS_PER_TURN {
+        match play_action(engine, ai, player_id) {
+            Some(result) => results.push(result),
+            None => break,
+        }
+    }
+    results
+}
+
+/// Let an AI take a single action for `player_id`, if it is their move and the
+/// game is not over. A rejected choice falls back to passing or ending the
+/// phase so the game always moves on.
+pub fn play_action(engine: &mut GameEngine, ai: &mut dyn AiPlayer, player_id: u32) -> Option<ActionResult> {
+    if engine.is_game_over().is_some() || engine.state.acting_player() != player_id {
+        return None;
+    }
+
+    let fallback = if engine.state.pending_attack.is_some() {
+        PlayerAction::PassResponse
+    } else {
+        PlayerAction::EndPhase
+    };
+    let action = ai.choose_action(&engine.state, player_id).unwrap_or(fallback.clone());
+
+    match engine.process_action(player_id, action) {
+        Ok(result) => Some(result),
+        Err(_) => engine.process_action(player_id, fallback).ok(),
+    }
+}
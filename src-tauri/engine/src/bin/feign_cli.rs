@@ -0,0 +1,462 @@
+// Headless front end for the engine: hot-seat games in the terminal, bot-vs-bot
+// matches and game logs
+
+use feign_engine::ai::{self, AiKind};
+use feign_engine::core::{
+    build_deck, create_mock_deck, validate_deck, CardCatalog, DeckList, DeckRules, GameEngine, Replay, ReplayPlayer,
+};
+use feign_engine::models::*;
+use feign_engine::sim::{self, SimulatedDeck, SimulationConfig, DEFAULT_MAX_TURNS};
+use std::io::{self, BufRead, Write};
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage:
+  feign-cli play [--seed N] [--p1 NAME] [--p2 NAME] [--ai greedy|mcts] [--replay PATH]
+      Play a hot-seat game in the terminal; --ai seats a bot as player 2
+  feign-cli match [--seed N] [--p1-ai greedy|mcts] [--p2-ai greedy|mcts] [--games N]
+                  [--max-turns N] [--replay PATH] [--quiet]
+      Let two bots play; prints the log of a single game, or the tally of several
+  feign-cli simulate [--deck1 PATH] [--deck2 PATH] [--p1-ai greedy|mcts] [--p2-ai greedy|mcts]
+                     [--games N] [--seed N] [--max-turns N] [--format json|csv] [--out PATH]
+      Play many seeded games between two decks (text or deck code files that
+      follow the standard deck rules; the mock deck by default) and report win
+      rates, card and damage statistics
+  feign-cli log PATH
+      Print the game log of a replay file";
+
+const PLAY_HELP: &str = "\
+Commands:
+  creature ID COL   Play a creature from hand into a column (1-4)
+  feign ID COL      Play a feign face down behind a column
+  effect ID         Play a global effect
+  attack COL        Attack with the creature in a column
+  reveal COL        Reveal a feign in response to an attack
+  pass              Let a pending attack through
+  end               End the current phase
+  N                 Take action N from the `actions` list
+  actions           List every legal action
+  undo / redo       Take back or repeat an action this turn
+  board             Show the board again
+  log               Show the game log
+  help              Show this help
+  quit              Leave the game";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("play") => Options::parse(&args[1..]).and_then(|options| play(&options)),
+        Some("match") => Options::parse(&args[1..]).and_then(|options| run_match(&options)),
+        Some("simulate") => Options::parse(&args[1..]).and_then(|options| simulate(&options)),
+        Some("log") => match &args[1..] {
+            [path] => print_replay_log(path),
+            _ => Err(USAGE.to_string()),
+        },
+        _ => Err(USAGE.to_string()),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Command-line flags shared by `play`, `match` and `simulate`
+#[derive(Debug, Clone)]
+struct Options {
+    seed: Option<u64>,
+    player1_name: String,
+    player2_name: String,
+    player1_ai: Option<AiKind>,
+    player2_ai: Option<AiKind>,
+    games: Option<u32>,
+    max_turns: u32,
+    replay_path: Option<String>,
+    quiet: bool,
+    deck_paths: [Option<String>; 2],
+    format: ReportFormat,
+    out_path: Option<String>,
+}
+
+/// Output format of `simulate`
+#[derive(Debug, Clone, Copy)]
+enum ReportFormat {
+    Json,
+    Csv,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Options, String> {
+        let mut options = Options {
+            seed: None,
+            player1_name: "Player 1".to_string(),
+            player2_name: "Player 2".to_string(),
+            player1_ai: None,
+            player2_ai: None,
+            games: None,
+            max_turns: DEFAULT_MAX_TURNS,
+            replay_path: None,
+            quiet: false,
+            deck_paths: [None, None],
+            format: ReportFormat::Json,
+            out_path: None,
+        };
+
+        let mut args = args.iter();
+        while let Some(flag) = args.next() {
+            if flag == "--quiet" {
+                options.quiet = true;
+                continue;
+            }
+            let value = args.next().ok_or_else(|| format!("{} needs a value\n\n{}", flag, USAGE))?;
+            match flag.as_str() {
+                "--seed" => options.seed = Some(parse_number(flag, value)?),
+                "--p1" => options.player1_name = value.clone(),
+                "--p2" => options.player2_name = value.clone(),
+                "--ai" | "--p2-ai" => options.player2_ai = Some(parse_ai(value)?),
+                "--p1-ai" => options.player1_ai = Some(parse_ai(value)?),
+                "--games" => options.games = Some(parse_number(flag, value)?),
+                "--max-turns" => options.max_turns = parse_number(flag, value)?,
+                "--replay" => options.replay_path = Some(value.clone()),
+                "--deck1" => options.deck_paths[0] = Some(value.clone()),
+                "--deck2" => options.deck_paths[1] = Some(value.clone()),
+                "--out" => options.out_path = Some(value.clone()),
+                "--format" => options.format = match value.to_ascii_lowercase().as_str() {
+                    "json" => ReportFormat::Json,
+                    "csv" => ReportFormat::Csv,
+                    _ => return Err(format!("Unknown format {} (expected json or csv)", value)),
+                },
+                _ => return Err(format!("Unknown option {}\n\n{}", flag, USAGE)),
+            }
+        }
+        Ok(options)
+    }
+
+    fn new_engine(&self, seed: Option<u64>) -> GameEngine {
+        GameEngine::with_catalog(self.player1_name.clone(), self.player2_name.clone(), CardCatalog::bundled(), seed)
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("{} expects a number, got {}", flag, value))
+}
+
+fn parse_ai(value: &str) -> Result<AiKind, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "greedy" => Ok(AiKind::Greedy),
+        "mcts" => Ok(AiKind::Mcts),
+        _ => Err(format!("Unknown AI {} (expected greedy or mcts)", value)),
+    }
+}
+
+/// Write the replay of a finished game if one was asked for
+fn write_replay(engine: &GameEngine, path: Option<&str>) -> Result<(), String> {
+    let Some(path) = path else { return Ok(()) };
+    let replay = engine.replay().ok_or("This game was not recorded")?;
+    std::fs::write(path, replay.to_json()).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    println!("Replay written to {}", path);
+    Ok(())
+}
+
+/// Play a game in the terminal, both seats sharing the keyboard unless a bot
+/// takes player 2
+fn play(options: &Options) -> Result<(), String> {
+    let mut engine = options.new_engine(options.seed);
+    let mut bot = options.player2_ai.map(|kind| kind.create());
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    let mut log_shown = 0;
+
+    println!("{}", PLAY_HELP);
+    let mut shown_to = None;
+    while engine.is_game_over().is_none() {
+        let player_id = engine.state.acting_player();
+        if let (Some(bot), 2) = (bot.as_mut(), player_id) {
+            if ai::play_action(&mut engine, bot.as_mut(), player_id).is_none() {
+                return Err("The AI could not move".to_string());
+            }
+            print_new_log(&engine, &mut log_shown);
+            continue;
+        }
+
+        if shown_to != Some((player_id, engine.state.events.len())) {
+            print_new_log(&engine, &mut log_shown);
+            print_view(&engine.state.view_for(player_id));
+            shown_to = Some((player_id, engine.state.events.len()));
+        }
+        print!("{}> ", engine.state.player_name(player_id));
+        io::stdout().flush().map_err(|e| e.to_string())?;
+
+        let Some(line) = lines.next() else { return Ok(()) };
+        let line = line.map_err(|e| e.to_string())?;
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let result = match words.as_slice() {
+            [] => continue,
+            ["quit"] | ["exit"] => return Ok(()),
+            ["help"] => {
+                println!("{}", PLAY_HELP);
+                continue;
+            }
+            ["board"] => {
+                print_view(&engine.state.view_for(player_id));
+                continue;
+            }
+            ["log"] => {
+                engine.state.game_log().iter().for_each(|line| println!("  {}", line));
+                continue;
+            }
+            ["actions"] => {
+                for (index, action) in engine.legal_actions(player_id).iter().enumerate() {
+                    println!("  {}. {}", index + 1, describe_action(&engine.state, action));
+                }
+                continue;
+            }
+            ["undo"] => engine.undo(player_id),
+            ["redo"] => engine.redo(player_id),
+            words => match parse_action(&engine, player_id, words) {
+                Ok(action) => engine.process_action(player_id, action),
+                Err(message) => {
+                    println!("{}", message);
+                    continue;
+                }
+            },
+        };
+
+        match result {
+            Ok(result) => println!("{}", result.message),
+            Err(error) => println!("{}", error),
+        }
+    }
+
+    print_new_log(&engine, &mut log_shown);
+    if let Some(winner) = engine.is_game_over() {
+        println!("{} wins!", engine.state.player_name(winner));
+    }
+    write_replay(&engine, options.replay_path.as_deref())
+}
+
+/// Turn a typed command into an action. Columns are typed 1-based, like the log shows them.
+fn parse_action(engine: &GameEngine, player_id: u32, words: &[&str]) -> Result<PlayerAction, String> {
+    let number = |word: &str| word.parse::<usize>().map_err(|_| format!("Not a number: {}", word));
+    let column = |word: &str| match number(word)? {
+        column @ 1..=BOARD_COLUMNS => Ok(column - 1),
+        _ => Err(format!("Columns go from 1 to {}", BOARD_COLUMNS)),
+    };
+
+    match words {
+        ["creature", id, col] => Ok(PlayerAction::PlayCreature { instance_id: number(id)? as u32, column: column(col)? }),
+        ["feign", id, col] => Ok(PlayerAction::PlayFeign { instance_id: number(id)? as u32, column: column(col)? }),
+        ["effect", id] => Ok(PlayerAction::PlayEffect { instance_id: number(id)? as u32 }),
+        ["attack", col] => Ok(PlayerAction::Attack { column: column(col)? }),
+        ["reveal", col] => Ok(PlayerAction::RevealFeign { column: column(col)? }),
+        ["pass"] => Ok(PlayerAction::PassResponse),
+        ["end"] => Ok(PlayerAction::EndPhase),
+        [index] if index.chars().all(|c| c.is_ascii_digit()) => {
+            let actions = engine.legal_actions(player_id);
+            number(index)?
+                .checked_sub(1)
+                .and_then(|index| actions.get(index).cloned())
+                .ok_or_else(|| format!("No action {}; type `actions` to list them", index))
+        }
+        _ => Err("Unknown command; type `help` for the list".to_string()),
+    }
+}
+
+/// One line describing an action, naming the card it plays
+fn describe_action(state: &GameState, action: &PlayerAction) -> String {
+    let player = if state.acting_player() == 1 { &state.player1 } else { &state.player2 };
+    let card_name = |instance_id: &u32| {
+        player.hand.iter()
+            .find(|card| card.instance_id == *instance_id)
+            .map_or("?".to_string(), |card| card.name.clone())
+    };
+
+    match action {
+        PlayerAction::PlayCreature { instance_id, column } => {
+            format!("creature {} {}  (play {} in column {})", instance_id, column + 1, card_name(instance_id), column + 1)
+        }
+        PlayerAction::PlayFeign { instance_id, column } => {
+            format!("feign {} {}  (set {} behind column {})", instance_id, column + 1, card_name(instance_id), column + 1)
+        }
+        PlayerAction::PlayEffect { instance_id } => {
+            format!("effect {}  (play {})", instance_id, card_name(instance_id))
+        }
+        PlayerAction::Attack { column } => format!("attack {}", column + 1),
+        PlayerAction::RevealFeign { column } => format!("reveal {}", column + 1),
+        PlayerAction::PassResponse => "pass".to_string(),
+        PlayerAction::EndPhase => "end".to_string(),
+    }
+}
+
+/// Print the log lines recorded since the last call
+fn print_new_log(engine: &GameEngine, shown: &mut usize) {
+    for event in engine.state.events.iter().skip(*shown) {
+        println!("  {}", event.describe(|id| engine.state.player_name(id)));
+    }
+    *shown = engine.state.events.len();
+}
+
+/// Print the board, hand and status as one player sees them
+fn print_view(view: &PlayerView) {
+    let (me, opponent) = if view.viewer_id == 1 { (&view.player1, &view.player2) } else { (&view.player2, &view.player1) };
+
+    println!();
+    println!("Turn {} - {:?} phase - {}'s turn", view.turn_number, view.phase,
+        if view.current_player == me.id { &me.name } else { &opponent.name });
+    if let Some(effect) = &view.global_effect {
+        println!("Global effect: {} ({} turns left)", effect.card.name, effect.remaining_duration);
+    }
+    print_player(opponent);
+    print_board(&opponent.board, true);
+    println!("  {}", "-".repeat(BOARD_COLUMNS * 18));
+    print_board(&me.board, false);
+    print_player(me);
+    for card in &me.hand {
+        let stats = match (card.attack, card.defense) {
+            (Some(attack), Some(defense)) => format!(" {}/{}", attack, defense),
+            _ => String::new(),
+        };
+        println!("  #{:<3} {} ({:?}, {:?}, {} mana){} - {}",
+            card.instance_id, card.name, card.card_type, card.color, card.mana_cost, stats, card.description);
+    }
+    if let Some(pending) = &view.pending_attack {
+        println!("Attack pending in column {}: reveal a feign or pass", pending.column + 1);
+    }
+}
+
+fn print_player(player: &PlayerInfoView) {
+    println!("{}: {} life, {} mana, {} shield, {} in hand, {} in deck",
+        player.name, player.life, player.mana, player.shield, player.hand_size, player.deck_size);
+}
+
+/// Print a board's two rows; the opponent's is drawn with its feigns on the far side
+fn print_board(board: &BoardView, far_side: bool) {
+    let creatures: String = board.creatures.iter().map(|slot| match slot {
+        Some(creature) => format!("{:<18}", format!("[{} {}/{}]",
+            creature.card.name, creature.current_attack, creature.current_defense)),
+        None => format!("{:<18}", "[ ]"),
+    }).collect();
+    let feigns: String = board.feigns.iter().map(|slot| match slot {
+        Some(FeignView { card: Some(card), is_revealed }) => {
+            format!("{:<18}", format!("({}{})", card.name, if *is_revealed { "" } else { ", hidden" }))
+        }
+        Some(FeignView { card: None, .. }) => format!("{:<18}", "(face down)"),
+        None => format!("{:<18}", "( )"),
+    }).collect();
+
+    let rows = if far_side { [feigns, creatures] } else { [creatures, feigns] };
+    for row in rows {
+        println!("  {}", row.trim_end());
+    }
+}
+
+/// Play bot-vs-bot games. A single game prints its log; several print a tally.
+/// Game N is dealt from seed + N when a seed is given.
+fn run_match(options: &Options) -> Result<(), String> {
+    let kinds = [options.player1_ai.unwrap_or_default(), options.player2_ai.unwrap_or_default()];
+    let mut wins = [0u32; 2];
+    let mut draws = 0;
+
+    let games = options.games.unwrap_or(1);
+    for game in 0..games {
+        let mut engine = options.new_engine(options.seed.map(|seed| seed.wrapping_add(game as u64)));
+        let mut bots = kinds.map(AiKind::create);
+        let winner = sim::play_bot_game(&mut engine, &mut bots, options.max_turns);
+
+        if games == 1 {
+            if !options.quiet {
+                engine.state.game_log().iter().for_each(|line| println!("{}", line));
+            }
+            match winner {
+                Some(winner) => println!("{} wins on turn {}",
+                    engine.state.player_name(winner), engine.state.turn_number),
+                None => println!("Draw after {} turns", options.max_turns),
+            }
+            write_replay(&engine, options.replay_path.as_deref())?;
+        }
+        match winner {
+            Some(winner) => wins[winner as usize - 1] += 1,
+            None => draws += 1,
+        }
+    }
+
+    if games > 1 {
+        println!("{} games", games);
+        println!("{} ({:?}): {} wins", options.player1_name, kinds[0], wins[0]);
+        println!("{} ({:?}): {} wins", options.player2_name, kinds[1], wins[1]);
+        println!("Draws: {}", draws);
+    }
+    Ok(())
+}
+
+/// Games `simulate` plays unless told otherwise
+const DEFAULT_SIMULATED_GAMES: u32 = 1000;
+
+/// Run a batch simulation between two decks and print or write the report
+fn simulate(options: &Options) -> Result<(), String> {
+    let catalog = CardCatalog::bundled();
+    let mut decks = Vec::new();
+    for (index, path) in options.deck_paths.iter().enumerate() {
+        let (name, cards) = match path {
+            Some(path) => {
+                let text = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+                let deck = DeckList::import(&text, catalog).map_err(|errors| {
+                    let lines: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+                    format!("{}:\n{}", path, lines.join("\n"))
+                })?;
+                validate_deck(&deck, catalog, &DeckRules::default()).map_err(|violations| {
+                    let lines: Vec<String> = violations.iter().map(|v| v.to_string()).collect();
+                    format!("{} is not a legal deck:\n{}", path, lines.join("\n"))
+                })?;
+                (path.clone(), build_deck(&deck, catalog))
+            }
+            None => (format!("Mock deck {}", index + 1), create_mock_deck(catalog)),
+        };
+        let ai = [options.player1_ai, options.player2_ai][index].unwrap_or_default();
+        decks.push(SimulatedDeck { name, cards, ai });
+    }
+    let [deck1, deck2]: [SimulatedDeck; 2] = decks.try_into().expect("two decks");
+
+    let config = SimulationConfig {
+        decks: [deck1, deck2],
+        games: options.games.unwrap_or(DEFAULT_SIMULATED_GAMES),
+        seed: options.seed.unwrap_or_else(rand::random),
+        max_turns: options.max_turns,
+    };
+    let report = sim::run_with_progress(&config, |done| {
+        if !options.quiet && (done % 100 == 0 || done == config.games) {
+            eprintln!("{}/{} games", done, config.games);
+        }
+    });
+
+    let output = match options.format {
+        ReportFormat::Json => report.to_json(),
+        ReportFormat::Csv => report.to_csv(),
+    };
+    match &options.out_path {
+        Some(path) => {
+            std::fs::write(path, output).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+            eprintln!("Report written to {} (seed {})", path, config.seed);
+        }
+        None => print!("{}", output),
+    }
+    Ok(())
+}
+
+/// Replay a recorded game to its end and print its log
+fn print_replay_log(path: &str) -> Result<(), String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let replay = Replay::from_json(&text).map_err(|e| format!("{}: {}", path, e))?;
+
+    let length = replay.actions.len();
+    let mut player = ReplayPlayer::new(replay);
+    let frame = player.seek(length).map_err(|e| format!("{}: {}", path, e))?;
+    frame.state.game_log().iter().for_each(|line| println!("{}", line));
+    if let Some(winner) = GameEngine::from_state(frame.state.clone()).is_game_over() {
+        println!("{} won", frame.state.player_name(winner));
+    }
+    Ok(())
+}
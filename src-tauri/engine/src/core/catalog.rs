@@ -0,0 +1,286 @@
+use crate::models::*;
+use serde::Deserialize;
+use std::fmt;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Catalog file format version understood by this build
+pub const CATALOG_VERSION: u32 = 1;
+
+/// Card data shipped with the app
+const BUNDLED_CATALOG: &str = include_str!("../../cards/cards.json");
+
+/// Errors raised while loading or validating card data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CatalogError {
+    Io { source: String, message: String },
+    Parse { source: String, line: usize, column: usize, message: String },
+    UnsupportedVersion { source: String, found: u32 },
+    InvalidCard { source: String, index: usize, name: String, reason: String },
+    DuplicateId { source: String, id: u32 },
+    DuplicateName { source: String, name: String },
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CatalogError::Io { source, message } => {
+                write!(f, "{}: could not read card data: {}", source, message)
+            }
+            // serde_json messages already end with the line and column
+            CatalogError::Parse { source, message, .. } => {
+                write!(f, "{}: malformed card data: {}", source, message)
+            }
+            CatalogError::UnsupportedVersion { source, found } => {
+                write!(f, "{}: unsupported catalog version {} (expected {})", source, found, CATALOG_VERSION)
+            }
+            CatalogError::InvalidCard { source, index, name, reason } => {
+                write!(f, "{}: card #{} ({}): {}", source, index, name, reason)
+            }
+            CatalogError::DuplicateId { source, id } => {
+                write!(f, "{}: card id {} is defined more than once", source, id)
+            }
+            CatalogError::DuplicateName { source, name } => {
+                write!(f, "{}: card name \"{}\" is defined more than once", source, name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CatalogError {}
+
+/// On-disk layout of a catalog file; cards are decoded one by one so a bad
+/// entry can be reported by position
+#[derive(Deserialize)]
+struct CatalogFile {
+    version: u32,
+    cards: Vec<serde_json::Value>,
+}
+
+/// The set of card definitions available to games and deck building
+#[derive(Debug, Clone, Default)]
+pub struct CardCatalog {
+    cards: Vec<Card>,
+}
+
+impl CardCatalog {
+    /// The catalog bundled with the app
+    pub fn bundled() -> &'static CardCatalog {
+        static BUNDLED: OnceLock<CardCatalog> = OnceLock::new();
+        BUNDLED.get_or_init(|| {
+            CardCatalog::from_json("bundled cards.json", BUNDLED_CATALOG)
+                .expect("bundled card catalog is invalid")
+        })
+    }
+
+    /// The bundled catalog extended with every `*.json` file in a user directory.
+    /// A missing directory is not an error.
+    pub fn bundled_with_user_dir(dir: &Path) -> Result<CardCatalog, CatalogError> {
+        let mut catalog = CardCatalog::bundled().clone();
+        if !dir.is_dir() {
+            return Ok(catalog);
+        }
+
+        let io_error = |e: std::io::Error| CatalogError::Io {
+            source: dir.display().to_string(),
+            message: e.to_string(),
+        };
+        let mut paths: Vec<_> = std::fs::read_dir(dir)
+            .map_err(io_error)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            let source = path.display().to_string();
+            let text = std::fs::read_to_string(&path).map_err(|e| CatalogError::Io {
+                source: source.clone(),
+                message: e.to_string(),
+            })?;
+            let extra = CardCatalog::from_json(&source, &text)?;
+            catalog.extend(&source, extra)?;
+        }
+        Ok(catalog)
+    }
+
+    /// Parse and validate a catalog file; `source` names it in error messages
+    pub fn from_json(source: &str, text: &str) -> Result<CardCatalog, CatalogError> {
+        let parse_error = |e: serde_json::Error| CatalogError::Parse {
+            source: source.to_string(),
+            line: e.line(),
+            column: e.column(),
+            message: e.to_string(),
+        };
+
+        let file: CatalogFile = serde_json::from_str(text).map_err(parse_error)?;
+        if file.version != CATALOG_VERSION {
+            return Err(CatalogError::UnsupportedVersion {
+                source: source.to_string(),
+                found: file.version,
+            });
+        }
+
+        let mut catalog = CardCatalog::default();
+        for (index, value) in file.cards.into_iter().enumerate() {
+            let name = value.get("name")
+                .and_then(|name| name.as_str())
+                .unwrap_or("<unnamed>")
+                .to_string();
+            let card: Card = serde_json::from_value(value).map_err(|e| CatalogError::InvalidCard {
+                source: source.to_string(),
+                index,
+                name: name.clone(),
+                reason: e.to_string(),
+            })?;
+
+            if let Err(reason) = validate_card(&card) {
+                return Err(CatalogError::InvalidCard { source: source.to_string(), index, name, reason });
+            }
+            catalog.insert(source, card)?;
+        }
+        Ok(catalog)
+    }
+
+    /// Add every card from another catalog
+    fn extend(&mut self, source: &str, other: CardCatalog) -> Result<(), CatalogError> {
+        for card in other.cards {
+            self.insert(source, card)?;
+        }
+        Ok(())
+    }
+
+    /// Add a card, rejecting duplicate ids and names
+    fn insert(&mut self, source: &str, card: Card) -> Result<(), CatalogError> {
+        if self.get(card.id).is_some() {
+            return Err(CatalogError::DuplicateId { source: source.to_string(), id: card.id });
+        }
+        if self.find_by_name(&card.name).is_some() {
+            return Err(CatalogError::DuplicateName { source: source.to_string(), name: card.name });
+        }
+        self.cards.push(card);
+        Ok(())
+    }
+
+    /// Every card in the catalog, in load order
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    /// Look up a card by catalog id
+    pub fn get(&self, id: u32) -> Option<&Card> {
+        self.cards.iter().find(|card| card.id == id)
+    }
+
+    /// Look up a card by name, ignoring case
+    pub fn find_by_name(&self, name: &str) -> Option<&Card> {
+        self.cards.iter().find(|card| card.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// All cards of a color
+    pub fn by_color(&self, color: Color) -> Vec<&Card> {
+        self.cards.iter().filter(|card| card.color == color).collect()
+    }
+
+    /// All cards of a type
+    pub fn by_type(&self, card_type: &CardType) -> Vec<&Card> {
+        self.cards.iter().filter(|card| &card.card_type == card_type).collect()
+    }
+}
+
+/// Check the per-type rules a card definition must follow
+fn validate_card(card: &Card) -> Result<(), String> {
+    if card.id == 0 {
+        return Err("id 0 is reserved for tokens".to_string());
+    }
+    if card.id == HIDDEN_CARD_ID {
+        return Err(format!("id {} is reserved for hidden cards", HIDDEN_CARD_ID));
+    }
+    if card.name.trim().is_empty() {
+        return Err("name must not be empty".to_string());
+    }
+
+    match card.card_type {
+        CardType::Creature => {
+            if card.attack.is_none() || card.defense.is_none() {
+                return Err("creatures need attack and defense".to_string());
+            }
+            if card.duration.is_some() || !card.continuous_effects.is_empty() {
+                return Err("creatures cannot have a duration or continuous effects".to_string());
+            }
+            // Only feigns resolve effects, so any listed here would never happen
+            if !card.effects.is_empty() {
+                return Err("creatures cannot have effects".to_string());
+            }
+        }
+        CardType::Feign => {
+            if card.attack.is_some() || card.defense.is_some() || card.duration.is_some() {
+                return Err("feigns cannot have attack, defense or duration".to_string());
+            }
+            // Feigns never enter play as global effects, so these would never apply
+            if !card.continuous_effects.is_empty() {
+                return Err("feigns cannot have continuous effects".to_string());
+            }
+            if card.effects.is_empty() {
+                return Err("feigns need at least one effect".to_string());
+            }
+        }
+        CardType::Effect => {
+            if card.attack.is_some() || card.defense.is_some() {
+                return Err("effects cannot have attack or defense".to_string());
+            }
+            if card.duration.is_none_or(|duration| duration == 0) {
+                return Err("effects need a duration of at least 1".to_string());
+            }
+            if card.continuous_effects.is_empty() {
+                return Err("effects need at least one continuous effect".to_string());
+            }
+            if !card.effects.is_empty() {
+                return Err("effects act through continuous effects and cannot have effects".to_string());
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn effects_are_only_accepted_on_feigns() {
+        let catalog = |card: &str| CardCatalog::from_json("test", &format!(r#"{{"version": 1, "cards": [{}]}}"#, card));
+        let heal = r#""effects": [{"Heal": {"target": "Owner", "amount": 2}}]"#;
+
+        let creature = format!(
+            r#"{{"id": 1, "name": "Wolf", "card_type": "Creature", "color": "Verdant", "mana_cost": 2,
+                "description": "", "attack": 3, "defense": 2, {}}}"#,
+            heal
+        );
+        let effect = format!(
+            r#"{{"id": 2, "name": "Growth", "card_type": "Effect", "color": "Verdant", "mana_cost": 3,
+                "description": "", "duration": 3,
+                "continuous_effects": [{{"DamageReduction": {{"amount": 1}}}}], {}}}"#,
+            heal
+        );
+        let feign = format!(
+            r#"{{"id": 3, "name": "Mend", "card_type": "Feign", "color": "Ivory", "mana_cost": 1,
+                "description": "", {}}}"#,
+            heal
+        );
+
+        assert!(matches!(catalog(&creature), Err(CatalogError::InvalidCard { .. })));
+        assert!(matches!(catalog(&effect), Err(CatalogError::InvalidCard { .. })));
+        assert!(catalog(&feign).is_ok());
+    }
+
+    #[test]
+    fn continuous_effects_are_only_accepted_on_effect_cards() {
+        let catalog = |card: &str| CardCatalog::from_json("test", &format!(r#"{{"version": 1, "cards": [{}]}}"#, card));
+        let feign = r#"{"id": 3, "name": "Mend", "card_type": "Feign", "color": "Ivory", "mana_cost": 1,
+            "description": "", "effects": [{"Heal": {"target": "Owner", "amount": 2}}],
+            "continuous_effects": [{"DamageReduction": {"amount": 1}}]}"#;
+
+        assert!(matches!(catalog(feign), Err(CatalogError::InvalidCard { .. })));
+    }
+}
@@ -0,0 +1,276 @@
+use crate::models::*;
+use crate::core::effects::EffectResolver;
+
+/// Result of a combat resolution
+pub struct CombatResult {
+    pub summary: String,
+    pub events: Vec<GameEvent>,
+}
+
+/// Handles combat resolution between creatures
+pub struct CombatResolver;
+
+impl CombatResolver {
+    /// Resolve combat for an attacking creature in a specific column
+    pub fn resolve_combat(state: &mut GameState, attacking_player: u32, column: usize) -> CombatResult {
+        let mut events = Vec::new();
+        let reduction = EffectResolver::damage_reduction(state);
+        
+        // Get references to both players
+        let (attacker, defender) = if attacking_player == 1 {
+            (&mut state.player1, &mut state.player2)
+        } else {
+            (&mut state.player2, &mut state.player1)
+        };
+
+        // Check if there's an attacking creature
+        let attacking_creature = match attacker.board.creatures.get(column) {
+            Some(Some(creature)) => creature.clone(),
+            _ => {
+                return CombatResult {
+                    summary: "No creature to attack with".to_string(),
+                    events: Vec::new(),
+                };
+            }
+        };
+
+        events.push(GameEvent::CreatureAttacked {
+            player_id: attacker.id,
+            column,
+            name: attacking_creature.card.name.clone(),
+            attack: attacking_creature.current_attack,
+        });
+
+        // Check if there's a defending creature in the same column
+        let defending_creature = defender.board.creatures[column].clone();
+
+        match defending_creature {
+            Some(mut defending_creature) => {
+                // Creature vs Creature combat
+                events.push(GameEvent::CreatureBlocked {
+                    player_id: defender.id,
+                    column,
+                    name: defending_creature.card.name.clone(),
+                    defense: defending_creature.current_defense,
+                });
+
+                let attacker_damage = attacking_creature.current_attack.saturating_sub(reduction);
+                let defender_damage = defending_creature.current_attack.saturating_sub(reduction);
+
+                // Apply damage
+                if attacker_damage >= defending_creature.current_defense {
+                    // Defending creature dies
+                    defender.board.creatures[column] = None;
+                    events.push(GameEvent::DamageDealt {
+                        source: attacking_creature.card.name.clone(),
+                        target: EventTarget::Creature {
+                            player_id: defender.id,
+                            instance_id: defending_creature.card.instance_id,
+                            name: defending_creature.card.name.clone(),
+                        },
+                        amount: attacker_damage,
+                    });
+                    events.push(GameEvent::CreatureDestroyed {
+                        player_id: defender.id,
+                        instance_id: defending_creature.card.instance_id,
+                        name: defending_creature.card.name.clone(),
+                    });
+
+                    // Calculate excess damage to player
+                    let excess_damage = attacker_damage - defending_creature.current_defense;
+                    if excess_damage > 0 {
+                        let life_lost = defender.take_damage(excess_damage);
+                        events.push(GameEvent::DamageDealt {
+                            source: attacking_creature.card.name.clone(),
+                            target: EventTarget::Player { player_id: defender.id, life: defender.life },
+                            amount: life_lost,
+                        });
+                    }
+                } else {
+                    // Defending creature survives with reduced defense
+                    defending_creature.damage += attacker_damage;
+                    defending_creature.current_defense -= attacker_damage;
+                    defender.board.creatures[column] = Some(defending_creature.clone());
+                    events.push(GameEvent::DamageDealt {
+                        source: attacking_creature.card.name.clone(),
+                        target: EventTarget::Creature {
+                            player_id: defender.id,
+                            instance_id: defending_creature.card.instance_id,
+                            name: defending_creature.card.name.clone(),
+                        },
+                        amount: attacker_damage,
+                    });
+                    events.push(GameEvent::CreatureSurvived {
+                        player_id: defender.id,
+                        instance_id: defending_creature.card.instance_id,
+                        name: defending_creature.card.name.clone(),
+                        defense: defending_creature.current_defense,
+                    });
+                }
+
+                // Check if attacking creature takes damage back
+                if defender_damage > 0 {
+                    events.push(GameEvent::DamageDealt {
+                        source: defending_creature.card.name.clone(),
+                        target: EventTarget::Creature {
+                            player_id: attacker.id,
+                            instance_id: attacking_creature.card.instance_id,
+                            name: attacking_creature.card.name.clone(),
+                        },
+                        amount: defender_damage,
+                    });
+                }
+                if defender_damage >= attacking_creature.current_defense {
+                    // Attacking creature dies
+                    attacker.board.creatures[column] = None;
+                    events.push(GameEvent::CreatureDestroyed {
+                        player_id: attacker.id,
+                        instance_id: attacking_creature.card.instance_id,
+                        name: attacking_creature.card.name.clone(),
+                    });
+                } else if defender_damage > 0 {
+                    // Attacking creature survives with reduced defense
+                    let mut surviving_attacker = attacking_creature.clone();
+                    surviving_attacker.damage += defender_damage;
+                    surviving_attacker.current_defense -= defender_damage;
+                    attacker.board.creatures[column] = Some(surviving_attacker.clone());
+                    events.push(GameEvent::CreatureSurvived {
+                        player_id: attacker.id,
+                        instance_id: surviving_attacker.card.instance_id,
+                        name: surviving_attacker.card.name.clone(),
+                        defense: surviving_attacker.current_defense,
+                    });
+                }
+
+                CombatResult {
+                    summary: "Combat resolved".to_string(),
+                    events,
+                }
+            }
+            None => {
+                // Direct attack to player
+                let damage = defender.take_damage(attacking_creature.current_attack.saturating_sub(reduction));
+                events.push(GameEvent::DamageDealt {
+                    source: attacking_creature.card.name.clone(),
+                    target: EventTarget::Player { player_id: defender.id, life: defender.life },
+                    amount: damage,
+                });
+
+                CombatResult {
+                    summary: format!("Direct attack for {} damage", damage),
+                    events,
+                }
+            }
+        }
+    }
+
+    /// Reveal the feign in a column and execute its effects against `target_column`
+    /// (the feign's own column, or the attacked lane when revealed in response)
+    pub fn apply_feign_effects(state: &mut GameState, player_id: u32, column: usize, target_column: usize) -> Vec<GameEvent> {
+        let feign_card = {
+            let player = if player_id == 1 {
+                &mut state.player1
+            } else {
+                &mut state.player2
+            };
+
+            match player.board.feigns.get_mut(column) {
+                Some(Some(feign)) if !feign.is_revealed => {
+                    feign.is_revealed = true;
+                    feign.card.clone()
+                }
+                _ => return Vec::new(),
+            }
+        };
+
+        let mut events = vec![GameEvent::FeignRevealed { player_id, card: feign_card.clone(), column }];
+        events.extend(EffectResolver::resolve_card(state, player_id, target_column, &feign_card));
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::game_engine::tests::{bare_game, creature, place_creature};
+    use crate::models::*;
+
+    const FOREST_WOLF: u32 = 1; // 3/2
+    const ANCIENT_TREANT: u32 = 2; // 4/6
+    const VINE_SPRITE: u32 = 3; // 1/1
+    const FLAME_DRAGON: u32 = 5; // 7/5
+    const HOLY_KNIGHT: u32 = 10; // 3/3
+
+    #[test]
+    fn attackers_only_fight_the_creature_in_their_lane() {
+        let mut engine = bare_game(GamePhase::Attack);
+        place_creature(&mut engine, 1, FOREST_WOLF, 0);
+        place_creature(&mut engine, 2, HOLY_KNIGHT, 0);
+        place_creature(&mut engine, 2, VINE_SPRITE, 1);
+
+        engine.process_action(1, PlayerAction::Attack { column: 0 }).unwrap();
+
+        // The wolf and the knight trade; the sprite next door is untouched
+        assert!(creature(&engine, 1, 0).is_none());
+        assert!(creature(&engine, 2, 0).is_none());
+        assert_eq!(creature(&engine, 2, 1).unwrap().current_defense, 1);
+        assert_eq!(engine.state.player2.life, 20);
+    }
+
+    #[test]
+    fn excess_damage_tramples_over_a_destroyed_blocker() {
+        let mut engine = bare_game(GamePhase::Attack);
+        place_creature(&mut engine, 1, FLAME_DRAGON, 2);
+        place_creature(&mut engine, 2, VINE_SPRITE, 2);
+
+        engine.process_action(1, PlayerAction::Attack { column: 2 }).unwrap();
+
+        assert!(creature(&engine, 2, 2).is_none());
+        assert_eq!(engine.state.player2.life, 14);
+        let dragon = creature(&engine, 1, 2).unwrap();
+        assert_eq!((dragon.current_defense, dragon.damage), (4, 1));
+        assert!(dragon.is_tapped);
+    }
+
+    #[test]
+    fn surviving_blockers_keep_their_damage_and_stop_trample() {
+        let mut engine = bare_game(GamePhase::Attack);
+        place_creature(&mut engine, 1, FOREST_WOLF, 1);
+        place_creature(&mut engine, 2, ANCIENT_TREANT, 1);
+
+        engine.process_action(1, PlayerAction::Attack { column: 1 }).unwrap();
+
+        let treant = creature(&engine, 2, 1).unwrap();
+        assert_eq!((treant.current_defense, treant.damage), (3, 3));
+        assert!(creature(&engine, 1, 1).is_none());
+        assert_eq!(engine.state.player2.life, 20);
+    }
+
+    #[test]
+    fn unblocked_attacks_hit_the_shield_before_life() {
+        let mut engine = bare_game(GamePhase::Attack);
+        place_creature(&mut engine, 1, FOREST_WOLF, 3);
+        place_creature(&mut engine, 2, HOLY_KNIGHT, 2);
+        engine.state.player2.shield = 2;
+
+        engine.process_action(1, PlayerAction::Attack { column: 3 }).unwrap();
+
+        assert_eq!((engine.state.player2.shield, engine.state.player2.life), (0, 19));
+        assert!(engine.state.events.iter().any(|event| matches!(
+            event,
+            GameEvent::DamageDealt { target: EventTarget::Player { player_id: 2, life: 19 }, amount: 1, .. }
+        )));
+    }
+
+    #[test]
+    fn creatures_attack_once_per_turn() {
+        let mut engine = bare_game(GamePhase::Attack);
+        place_creature(&mut engine, 1, ANCIENT_TREANT, 0);
+
+        engine.process_action(1, PlayerAction::Attack { column: 0 }).unwrap();
+        assert_eq!(
+            engine.process_action(1, PlayerAction::Attack { column: 0 }).unwrap_err(),
+            GameError::CreatureAlreadyAttacked { column: 0 }
+        );
+        assert_eq!(engine.process_action(1, PlayerAction::Attack { column: 1 }).unwrap_err(), GameError::NoCreature { column: 1 });
+    }
+}
@@ -0,0 +1,474 @@
+use crate::models::*;
+use crate::core::catalog::CardCatalog;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Deck code format version understood by this build
+pub const DECK_CODE_VERSION: u8 = 1;
+
+/// Order of the section headers in exported deck lists
+const SECTIONS: [(CardType, &str); 3] = [
+    (CardType::Creature, "Creatures"),
+    (CardType::Feign, "Feigns"),
+    (CardType::Effect, "Effects"),
+];
+
+/// Order of the color headers within a section
+const COLORS: [Color; 6] = [Color::Verdant, Color::Cinder, Color::Azure, Color::Ivory, Color::Umbral, Color::Violet];
+
+/// One line of a deck list: a catalog card and how many copies to include
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeckEntry {
+    pub card_id: u32,
+    pub count: u32,
+}
+
+/// A deck described by catalog ids and copy counts
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeckList {
+    pub entries: Vec<DeckEntry>,
+}
+
+impl DeckList {
+    /// Total number of cards in the deck. Summed as u64, since imported
+    /// counts can add up past u32.
+    pub fn size(&self) -> u64 {
+        self.entries.iter().map(|entry| u64::from(entry.count)).sum()
+    }
+
+    /// Add copies of a card, merging with an existing entry for the same card.
+    /// Returns the card's new count, or None and leaves the deck unchanged if
+    /// the count would overflow.
+    pub fn add(&mut self, card_id: u32, count: u32) -> Option<u32> {
+        match self.entries.iter_mut().find(|entry| entry.card_id == card_id) {
+            Some(entry) => {
+                entry.count = entry.count.checked_add(count)?;
+                Some(entry.count)
+            }
+            None => {
+                self.entries.push(DeckEntry { card_id, count });
+                Some(count)
+            }
+        }
+    }
+
+    /// Render the deck as text, one "3 Forest Wolf" line per card, grouped under
+    /// card type and color headers. Unknown card ids are left out.
+    pub fn to_text(&self, catalog: &CardCatalog) -> String {
+        let mut lines = Vec::new();
+        for (card_type, section) in &SECTIONS {
+            let cards: Vec<(&Card, u32)> = self.entries.iter()
+                .filter_map(|entry| catalog.get(entry.card_id).map(|card| (card, entry.count)))
+                .filter(|(card, _)| &card.card_type == card_type)
+                .collect();
+            if cards.is_empty() {
+                continue;
+            }
+
+            let total: u64 = cards.iter().map(|(_, count)| u64::from(*count)).sum();
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+            lines.push(format!("# {} ({})", section, total));
+            for color in COLORS {
+                let mut colored: Vec<&(&Card, u32)> = cards.iter().filter(|(card, _)| card.color == color).collect();
+                if colored.is_empty() {
+                    continue;
+                }
+                colored.sort_by(|(a, _), (b, _)| a.mana_cost.cmp(&b.mana_cost).then_with(|| a.name.cmp(&b.name)));
+
+                lines.push(format!("## {:?}", color));
+                for (card, count) in colored {
+                    lines.push(format!("{} {}", count, card.name));
+                }
+            }
+        }
+
+        let mut text = lines.join("\n");
+        text.push('\n');
+        text
+    }
+
+    /// Parse the text format produced by `to_text`. Blank lines and `#` headers
+    /// are ignored, and a line may write its count as "3" or "3x". Every bad
+    /// line is reported, not just the first.
+    pub fn from_text(text: &str, catalog: &CardCatalog) -> Result<DeckList, Vec<DeckImportError>> {
+        let mut deck = DeckList::default();
+        let mut errors = Vec::new();
+
+        for (index, raw) in text.lines().enumerate() {
+            let line = index + 1;
+            let trimmed = raw.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            let parsed = trimmed.split_once(char::is_whitespace).and_then(|(count, name)| {
+                let count = count.strip_suffix(['x', 'X']).unwrap_or(count);
+                count.parse::<u32>().ok().map(|count| (count, name.trim()))
+            });
+            let Some((count, name)) = parsed else {
+                errors.push(DeckImportError::MalformedLine { line, text: trimmed.to_string() });
+                continue;
+            };
+
+            match catalog.find_by_name(name) {
+                Some(card) => {
+                    if deck.add(card.id, count).is_none() {
+                        errors.push(DeckImportError::TooManyCopies { line: Some(line), name: card.name.clone() });
+                    }
+                }
+                None => errors.push(DeckImportError::UnknownCard { line, name: name.to_string() }),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(deck)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Parse either a deck code or the text format; codes never contain whitespace
+    pub fn import(input: &str, catalog: &CardCatalog) -> Result<DeckList, Vec<DeckImportError>> {
+        let input = input.trim();
+        if input.contains(char::is_whitespace) || input.starts_with('#') {
+            DeckList::from_text(input, catalog)
+        } else {
+            DeckList::from_code(input, catalog)
+        }
+    }
+
+    /// Encode the deck as a compact copy-paste code: a version byte followed by
+    /// (card id, count) pairs as varints, in URL-safe base64
+    pub fn to_code(&self) -> String {
+        let mut bytes = vec![DECK_CODE_VERSION];
+        for entry in &self.entries {
+            write_varint(&mut bytes, entry.card_id);
+            write_varint(&mut bytes, entry.count);
+        }
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    /// Decode a deck code produced by `to_code`, checking every id against the catalog
+    pub fn from_code(code: &str, catalog: &CardCatalog) -> Result<DeckList, Vec<DeckImportError>> {
+        let bytes = URL_SAFE_NO_PAD.decode(code.trim())
+            .map_err(|e| vec![DeckImportError::InvalidCode { reason: e.to_string() }])?;
+        let Some((&version, mut rest)) = bytes.split_first() else {
+            return Err(vec![DeckImportError::InvalidCode { reason: "code is empty".to_string() }]);
+        };
+        if version != DECK_CODE_VERSION {
+            return Err(vec![DeckImportError::InvalidCode {
+                reason: format!("unsupported deck code version {} (expected {})", version, DECK_CODE_VERSION),
+            }]);
+        }
+
+        let mut deck = DeckList::default();
+        let mut errors = Vec::new();
+        while !rest.is_empty() {
+            let entry = read_varint(&mut rest).and_then(|card_id| Ok((card_id, read_varint(&mut rest)?)));
+            let (card_id, count) = entry.map_err(|reason| vec![DeckImportError::InvalidCode { reason }])?;
+            match catalog.get(card_id) {
+                Some(card) => {
+                    if deck.add(card_id, count).is_none() {
+                        errors.push(DeckImportError::TooManyCopies { line: None, name: card.name.clone() });
+                    }
+                }
+                None => errors.push(DeckImportError::UnknownCardId { card_id }),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(deck)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// A problem found while importing a deck from text or a deck code
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeckImportError {
+    MalformedLine { line: usize, text: String },
+    UnknownCard { line: usize, name: String },
+    UnknownCardId { card_id: u32 },
+    InvalidCode { reason: String },
+    // Copies of one card, added up across lines or entries, past what a count can hold
+    TooManyCopies { line: Option<usize>, name: String },
+}
+
+impl fmt::Display for DeckImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeckImportError::MalformedLine { line, text } => {
+                write!(f, "line {}: expected \"<count> <card name>\", found \"{}\"", line, text)
+            }
+            DeckImportError::UnknownCard { line, name } => write!(f, "line {}: unknown card \"{}\"", line, name),
+            DeckImportError::UnknownCardId { card_id } => write!(f, "card id {} is not in the catalog", card_id),
+            DeckImportError::InvalidCode { reason } => write!(f, "invalid deck code: {}", reason),
+            DeckImportError::TooManyCopies { line: Some(line), name } => {
+                write!(f, "line {}: too many copies of {}", line, name)
+            }
+            DeckImportError::TooManyCopies { line: None, name } => write!(f, "too many copies of {}", name),
+        }
+    }
+}
+
+impl std::error::Error for DeckImportError {}
+
+/// Append a LEB128 varint
+fn write_varint(bytes: &mut Vec<u8>, mut value: u32) {
+    while value >= 0x80 {
+        bytes.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+/// Read a LEB128 varint, advancing the slice
+fn read_varint(bytes: &mut &[u8]) -> Result<u32, String> {
+    let mut value: u32 = 0;
+    for shift in (0..32).step_by(7) {
+        let (&byte, rest) = bytes.split_first().ok_or("code is truncated")?;
+        *bytes = rest;
+        // The fifth byte only has room for the top 4 bits
+        let bits = u32::from(byte & 0x7f);
+        if bits > u32::MAX >> shift {
+            return Err("a number in the code is too large".to_string());
+        }
+        value |= bits << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err("a number in the code is too large".to_string())
+}
+
+/// Construction rules a deck must satisfy to be played
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeckRules {
+    pub min_size: u32,
+    pub max_size: u32,
+    pub max_copies: u32,         // Copies allowed of any single card
+    pub max_colors: Option<u32>, // Distinct colors allowed; None is unlimited
+}
+
+impl Default for DeckRules {
+    fn default() -> Self {
+        DeckRules {
+            min_size: 20,
+            max_size: 40,
+            max_copies: 3,
+            max_colors: Some(2),
+        }
+    }
+}
+
+/// A reason a deck list breaks the rules
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeckViolation {
+    UnknownCard { card_id: u32 },
+    EmptyEntry { card_id: u32 },
+    TooFewCards { count: u64, min: u32 },
+    TooManyCards { count: u64, max: u32 },
+    TooManyCopies { name: String, count: u64, max: u32 },
+    TooManyColors { colors: Vec<Color>, max: u32 },
+}
+
+impl fmt::Display for DeckViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeckViolation::UnknownCard { card_id } => write!(f, "Card id {} is not in the catalog", card_id),
+            DeckViolation::EmptyEntry { card_id } => write!(f, "Card id {} is listed with 0 copies", card_id),
+            DeckViolation::TooFewCards { count, min } => {
+                write!(f, "Deck has {} cards but needs at least {}", count, min)
+            }
+            DeckViolation::TooManyCards { count, max } => {
+                write!(f, "Deck has {} cards but may have at most {}", count, max)
+            }
+            DeckViolation::TooManyCopies { name, count, max } => {
+                write!(f, "Deck has {} copies of {} but may have at most {}", count, name, max)
+            }
+            DeckViolation::TooManyColors { colors, max } => {
+                write!(f, "Deck uses {} colors ({:?}) but may use at most {}", colors.len(), colors, max)
+            }
+        }
+    }
+}
+
+/// Check a deck list against the rules, collecting every violation
+pub fn validate_deck(deck: &DeckList, catalog: &CardCatalog, rules: &DeckRules) -> Result<(), Vec<DeckViolation>> {
+    let mut violations = Vec::new();
+    let mut colors: Vec<Color> = Vec::new();
+    let mut copies: Vec<(u32, u64)> = Vec::new(); // (card id, count), merging repeated entries
+
+    for entry in &deck.entries {
+        let Some(card) = catalog.get(entry.card_id) else {
+            violations.push(DeckViolation::UnknownCard { card_id: entry.card_id });
+            continue;
+        };
+        if entry.count == 0 {
+            violations.push(DeckViolation::EmptyEntry { card_id: entry.card_id });
+            continue;
+        }
+
+        if !colors.contains(&card.color) {
+            colors.push(card.color);
+        }
+        match copies.iter_mut().find(|(id, _)| *id == card.id) {
+            Some((_, count)) => *count += u64::from(entry.count),
+            None => copies.push((card.id, entry.count.into())),
+        }
+    }
+
+    let size = deck.size();
+    if size < rules.min_size.into() {
+        violations.push(DeckViolation::TooFewCards { count: size, min: rules.min_size });
+    }
+    if size > rules.max_size.into() {
+        violations.push(DeckViolation::TooManyCards { count: size, max: rules.max_size });
+    }
+    for (card_id, count) in copies {
+        if count > rules.max_copies.into() {
+            let name = catalog.get(card_id).map(|card| card.name.clone()).unwrap_or_default();
+            violations.push(DeckViolation::TooManyCopies { name, count, max: rules.max_copies });
+        }
+    }
+    if let Some(max) = rules.max_colors {
+        if colors.len() as u32 > max {
+            violations.push(DeckViolation::TooManyColors { colors, max });
+        }
+    }
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+/// Expand a deck list into cards in list order; unknown ids are skipped, so
+/// validate the list first. The game engine shuffles decks when a game starts.
+pub fn build_deck(deck: &DeckList, catalog: &CardCatalog) -> Vec<Card> {
+    let mut cards = Vec::new();
+    for entry in &deck.entries {
+        if let Some(card) = catalog.get(entry.card_id) {
+            cards.extend(std::iter::repeat_n(card.clone(), entry.count as usize));
+        }
+    }
+    cards
+}
+
+/// The deck list players get unless they bring their own: two copies of every
+/// card of the catalog's first colors, as many colors and cards as the
+/// default rules allow
+pub fn mock_deck_list(catalog: &CardCatalog) -> DeckList {
+    let rules = DeckRules::default();
+    let mut colors: Vec<Color> = Vec::new();
+    let mut deck = DeckList::default();
+    for card in catalog.cards() {
+        if !colors.contains(&card.color) {
+            if rules.max_colors.is_some_and(|max| colors.len() as u32 >= max) {
+                continue;
+            }
+            colors.push(card.color);
+        }
+        if deck.size() + 2 <= rules.max_size.into() {
+            deck.add(card.id, 2);
+        }
+    }
+    deck
+}
+
+/// Create the mock deck's cards, in list order
+pub fn create_mock_deck(catalog: &CardCatalog) -> Vec<Card> {
+    build_deck(&mock_deck_list(catalog), catalog)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalog() -> &'static CardCatalog {
+        CardCatalog::bundled()
+    }
+
+    #[test]
+    fn repeated_cards_cannot_overflow_the_count() {
+        let card = &catalog().cards()[0];
+        let too_many = |line| vec![DeckImportError::TooManyCopies { line, name: card.name.clone() }];
+
+        let text = format!("{} {name}\n1 {name}\n", u32::MAX, name = card.name);
+        assert_eq!(DeckList::from_text(&text, catalog()), Err(too_many(Some(2))));
+
+        let crafted = DeckList {
+            entries: vec![DeckEntry { card_id: card.id, count: u32::MAX }, DeckEntry { card_id: card.id, count: 1 }],
+        };
+        assert_eq!(DeckList::from_code(&crafted.to_code(), catalog()), Err(too_many(None)));
+    }
+
+    #[test]
+    fn oversized_numbers_in_codes_are_invalid() {
+        let card_id = catalog().cards()[0].id as u8;
+        let code = |count: &[u8]| URL_SAFE_NO_PAD.encode([&[DECK_CODE_VERSION, card_id], count].concat());
+        let too_large = Err(vec![DeckImportError::InvalidCode { reason: "a number in the code is too large".to_string() }]);
+
+        // Five bytes hold 32 bits, so the last may only use its low 4
+        let largest = DeckList::from_code(&code(&[0xff, 0xff, 0xff, 0xff, 0x0f]), catalog()).unwrap();
+        assert_eq!(largest.entries[0].count, u32::MAX);
+        assert_eq!(DeckList::from_code(&code(&[0x83, 0x80, 0x80, 0x80, 0x10]), catalog()), too_large);
+        assert_eq!(DeckList::from_code(&code(&[0x80, 0x80, 0x80, 0x80, 0x80, 0x01]), catalog()), too_large);
+        assert_eq!(
+            DeckList::from_code(&code(&[0x80, 0x80]), catalog()),
+            Err(vec![DeckImportError::InvalidCode { reason: "code is truncated".to_string() }])
+        );
+    }
+
+    #[test]
+    fn imports_round_trip() {
+        let mut deck = DeckList::default();
+        for card in catalog().cards().iter().take(8) {
+            deck.add(card.id, 3);
+        }
+        assert_eq!(DeckList::from_code(&deck.to_code(), catalog()), Ok(deck.clone()));
+
+        let parsed = DeckList::from_text(&deck.to_text(catalog()), catalog()).unwrap();
+        assert_eq!(parsed.size(), deck.size());
+        for entry in &deck.entries {
+            assert!(parsed.entries.contains(entry), "{:?} missing", entry);
+        }
+    }
+
+    #[test]
+    fn oversized_entries_are_reported_once() {
+        let card = &catalog().cards()[0];
+        let deck = DeckList { entries: vec![DeckEntry { card_id: card.id, count: 50 }] };
+        assert_eq!(
+            validate_deck(&deck, catalog(), &DeckRules::default()),
+            Err(vec![
+                DeckViolation::TooManyCards { count: 50, max: 40 },
+                DeckViolation::TooManyCopies { name: card.name.clone(), count: 50, max: 3 },
+            ])
+        );
+    }
+
+    #[test]
+    fn mock_deck_follows_the_default_rules() {
+        let deck = mock_deck_list(catalog());
+        assert_eq!(validate_deck(&deck, catalog(), &DeckRules::default()), Ok(()));
+        assert_eq!(create_mock_deck(catalog()).len() as u64, deck.size());
+    }
+
+    #[test]
+    fn huge_counts_fail_validation() {
+        let card_id = catalog().cards()[0].id;
+        let deck = DeckList {
+            entries: vec![DeckEntry { card_id, count: u32::MAX }, DeckEntry { card_id: card_id + 1, count: u32::MAX }],
+        };
+        assert_eq!(deck.size(), 2 * u64::from(u32::MAX));
+        let violations = validate_deck(&deck, catalog(), &DeckRules::default()).unwrap_err();
+        assert!(violations.contains(&DeckViolation::TooManyCards { count: deck.size(), max: 40 }), "{:?}", violations);
+    }
+}
@@ -0,0 +1,527 @@
+use crate::models::*;
+
+/// Executes card effect primitives against the game state
+pub struct EffectResolver;
+
+impl EffectResolver {
+    /// Resolve every effect on a card owned by `owner_id`, played from `column`
+    pub fn resolve_card(state: &mut GameState, owner_id: u32, column: usize, card: &Card) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+        for effect in &card.effects {
+            Self::resolve_effect(state, owner_id, column, card, effect, &mut events);
+        }
+        events.extend(Self::refresh_creature_stats(state));
+        events
+    }
+
+    /// Resolve a single effect primitive
+    fn resolve_effect(
+        state: &mut GameState,
+        owner_id: u32,
+        column: usize,
+        source: &Card,
+        effect: &CardEffect,
+        events: &mut Vec<GameEvent>,
+    ) {
+        // Conditional effects only need to read the state before branching
+        if let CardEffect::IfGlobalEffectActive { then, otherwise } = effect {
+            let branch = if state.global_effect.is_some() { then } else { otherwise };
+            for inner in branch {
+                Self::resolve_effect(state, owner_id, column, source, inner, events);
+            }
+            return;
+        }
+
+        let reduction = Self::damage_reduction(state);
+        let (owner, opponent) = if owner_id == 1 {
+            (&mut state.player1, &mut state.player2)
+        } else {
+            (&mut state.player2, &mut state.player1)
+        };
+
+        match effect {
+            CardEffect::DealDamage { target, amount } => {
+                let amount = amount.saturating_sub(reduction);
+                match target {
+                    EffectTarget::Owner | EffectTarget::Opponent => {
+                        let player = if *target == EffectTarget::Owner { owner } else { opponent };
+                        let lost = player.take_damage(amount);
+                        events.push(GameEvent::DamageDealt {
+                            source: source.name.clone(),
+                            target: EventTarget::Player { player_id: player.id, life: player.life },
+                            amount: lost,
+                        });
+                    }
+                    _ => {
+                        for (player_id, creature) in Self::target_creatures(owner, opponent, *target, column) {
+                            creature.damage += amount;
+                            events.push(GameEvent::DamageDealt {
+                                source: source.name.clone(),
+                                target: EventTarget::Creature {
+                                    player_id,
+                                    instance_id: creature.card.instance_id,
+                                    name: creature.card.name.clone(),
+                                },
+                                amount,
+                            });
+                        }
+                    }
+                }
+            }
+            CardEffect::Heal { target, amount } => match target {
+                EffectTarget::Owner | EffectTarget::Opponent => {
+                    let player = if *target == EffectTarget::Owner { owner } else { opponent };
+                    player.life += amount;
+                    events.push(GameEvent::Healed {
+                        target: EventTarget::Player { player_id: player.id, life: player.life },
+                        amount: *amount,
+                    });
+                }
+                _ => {
+                    for (player_id, creature) in Self::target_creatures(owner, opponent, *target, column) {
+                        creature.damage = creature.damage.saturating_sub(*amount);
+                        events.push(GameEvent::Healed {
+                            target: EventTarget::Creature {
+                                player_id,
+                                instance_id: creature.card.instance_id,
+                                name: creature.card.name.clone(),
+                            },
+                            amount: *amount,
+                        });
+                    }
+                }
+            },
+            CardEffect::GrantMana { amount } => {
+                owner.mana += amount;
+                events.push(GameEvent::ManaGained { player_id: owner.id, amount: *amount });
+            }
+            CardEffect::ModifyStats { target, attack, defense } => {
+                for (player_id, creature) in Self::target_creatures(owner, opponent, *target, column) {
+                    creature.attack_modifier += attack;
+                    creature.defense_modifier += defense;
+                    events.push(GameEvent::StatsModified {
+                        player_id,
+                        instance_id: creature.card.instance_id,
+                        name: creature.card.name.clone(),
+                        attack: *attack,
+                        defense: *defense,
+                    });
+                }
+            }
+            CardEffect::Shield { amount } => {
+                owner.shield += amount;
+                events.push(GameEvent::ShieldRaised { player_id: owner.id, shield: owner.shield });
+            }
+            CardEffect::SummonToken { name, attack, defense, duration } => {
+                let open_column = if owner.board.creatures[column].is_none() {
+                    Some(column)
+                } else {
+                    owner.board.creatures.iter().position(|slot| slot.is_none())
+                };
+
+                match open_column {
+                    Some(token_column) => {
+                        // Tokens have no catalog entry, but still get an instance id
+                        let mut token = Creature::new(CardInstance {
+                            instance_id: state.instance_ids.allocate(),
+                            card: Card {
+                                id: 0,
+                                name: name.clone(),
+                                card_type: CardType::Creature,
+                                color: source.color,
+                                mana_cost: 0,
+                                description: format!("Token created by {}", source.name),
+                                attack: Some(*attack),
+                                defense: Some(*defense),
+                                duration: None,
+                                effects: Vec::new(),
+                                continuous_effects: Vec::new(),
+                            },
+                        });
+                        token.remaining_turns = *duration;
+                        owner.board.creatures[token_column] = Some(token);
+                        events.push(GameEvent::TokenSummoned {
+                            player_id: owner.id,
+                            name: name.clone(),
+                            attack: *attack,
+                            defense: *defense,
+                            column: token_column,
+                        });
+                    }
+                    None => {
+                        events.push(GameEvent::NoRoomForToken { player_id: owner.id, name: name.clone() });
+                    }
+                }
+            }
+            CardEffect::IfGlobalEffectActive { .. } => unreachable!("handled above"),
+        }
+    }
+
+    /// Collect the creatures an effect target refers to, with the id of the
+    /// player controlling each
+    fn target_creatures<'a>(
+        owner: &'a mut Player,
+        opponent: &'a mut Player,
+        target: EffectTarget,
+        column: usize,
+    ) -> Vec<(u32, &'a mut Creature)> {
+        let (owner_id, opponent_id) = (owner.id, opponent.id);
+        let owned = move |creature| (owner_id, creature);
+        let opposing = move |creature| (opponent_id, creature);
+
+        match target {
+            EffectTarget::Owner | EffectTarget::Opponent => Vec::new(),
+            EffectTarget::FriendlyCreature => {
+                owner.board.creatures.get_mut(column).and_then(|slot| slot.as_mut()).map(owned).into_iter().collect()
+            }
+            EffectTarget::OpposingCreature => {
+                opponent.board.creatures.get_mut(column).and_then(|slot| slot.as_mut()).map(opposing).into_iter().collect()
+            }
+            EffectTarget::AllFriendlyCreatures => owner.board.creatures.iter_mut().flatten().map(owned).collect(),
+            EffectTarget::AllEnemyCreatures => opponent.board.creatures.iter_mut().flatten().map(opposing).collect(),
+            EffectTarget::AllCreatures => owner.board.creatures.iter_mut().flatten().map(owned)
+                .chain(opponent.board.creatures.iter_mut().flatten().map(opposing))
+                .collect(),
+        }
+    }
+
+    /// Continuous effects of the active global effect, if any
+    fn continuous_effects(state: &GameState) -> Vec<ContinuousEffect> {
+        state.global_effect.as_ref()
+            .map(|effect| effect.card.continuous_effects.clone())
+            .unwrap_or_default()
+    }
+
+    /// Total damage reduction granted by the active global effect
+    pub fn damage_reduction(state: &GameState) -> u32 {
+        Self::continuous_effects(state).iter()
+            .map(|effect| match effect {
+                ContinuousEffect::DamageReduction { amount } => *amount,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Extra cards the active player draws during their draw phase
+    pub fn extra_draws(state: &GameState) -> u32 {
+        Self::continuous_effects(state).iter()
+            .map(|effect| match effect {
+                ContinuousEffect::ExtraDraw { count } => *count,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Apply the active global effect's end-of-turn triggers
+    pub fn apply_end_of_turn_triggers(state: &mut GameState) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+        let effect_name = match &state.global_effect {
+            Some(effect) => effect.card.name.clone(),
+            None => return events,
+        };
+
+        for effect in Self::continuous_effects(state) {
+            if let ContinuousEffect::DamageCreaturesEachTurn { amount } = effect {
+                for player in [&mut state.player1, &mut state.player2] {
+                    for creature in player.board.creatures.iter_mut().flatten() {
+                        creature.damage += amount;
+                        events.push(GameEvent::DamageDealt {
+                            source: effect_name.clone(),
+                            target: EventTarget::Creature {
+                                player_id: player.id,
+                                instance_id: creature.card.instance_id,
+                                name: creature.card.name.clone(),
+                            },
+                            amount,
+                        });
+                    }
+                }
+            }
+        }
+
+        events.extend(Self::refresh_creature_stats(state));
+        events
+    }
+
+    /// Recompute every creature's stats from its base stats plus active continuous
+    /// effects, then remove creatures whose defense has been reduced to zero
+    pub fn refresh_creature_stats(state: &mut GameState) -> Vec<GameEvent> {
+        let continuous_effects = Self::continuous_effects(state);
+        let mut events = Vec::new();
+
+        for player in [&mut state.player1, &mut state.player2] {
+            for slot in player.board.creatures.iter_mut() {
+                let Some(creature) = slot else { continue };
+
+                let (mut attack_bonus, mut defense_bonus) = (0, 0);
+                for effect in &continuous_effects {
+                    if let ContinuousEffect::StatModifier { color, attack, defense } = effect {
+                        if color.is_none_or(|color| color == creature.card.color) {
+                            attack_bonus += attack;
+                            defense_bonus += defense;
+                        }
+                    }
+                }
+                creature.refresh_stats(attack_bonus, defense_bonus);
+
+                if creature.current_defense == 0 {
+                    events.push(GameEvent::CreatureDestroyed {
+                        player_id: player.id,
+                        instance_id: creature.card.instance_id,
+                        name: creature.card.name.clone(),
+                    });
+                    *slot = None;
+                }
+            }
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::game_engine::tests::{bare_game, creature, end_turn, give_card, place_creature, place_feign};
+    use crate::core::GameEngine;
+    use crate::models::*;
+
+    const FOREST_WOLF: u32 = 1; // 3/2
+    const ANCIENT_TREANT: u32 = 2; // 4/6
+    const VINE_SPRITE: u32 = 3; // 1/1
+    const HOLY_KNIGHT: u32 = 10; // 3/3
+    const SHIELD_TRAP: u32 = 14;
+    const COUNTER_STRIKE: u32 = 15;
+    const MANA_BOOST: u32 = 16;
+    const ILLUSION: u32 = 17;
+    const SOUL_DRAIN: u32 = 18;
+    const ARCANE_RESONANCE: u32 = 19;
+    const MYSTIC_SCHOLAR: u32 = 13; // 1/3, Violet
+    const BLESSING_OF_GROWTH: u32 = 20; // +1/+1 to every creature
+    const INFERNO: u32 = 21; // 1 damage to every creature each turn, for 2 turns
+    const FROZEN_TIME: u32 = 22; // One extra draw
+    const DIVINE_PROTECTION: u32 = 23; // All damage reduced by 1
+    const CURSE_OF_WEAKNESS: u32 = 24; // -1/-0 to every creature
+    const ARCANE_AMPLIFICATION: u32 = 25; // +2/+2 to Violet creatures
+
+    fn stats(creature: Option<&Creature>) -> (u32, u32) {
+        let creature = creature.expect("no creature");
+        (creature.current_attack, creature.current_defense)
+    }
+
+    #[test]
+    fn shield_trap_absorbs_the_next_damage() {
+        let mut engine = bare_game(GamePhase::Placement);
+        place_feign(&mut engine, 2, SHIELD_TRAP, 0);
+        place_creature(&mut engine, 1, FOREST_WOLF, 1);
+
+        engine.state.current_player = 2;
+        engine.process_action(2, PlayerAction::RevealFeign { column: 0 }).unwrap();
+        assert_eq!(engine.state.player2.shield, 3);
+
+        engine.state.current_player = 1;
+        engine.state.phase = GamePhase::Attack;
+        engine.process_action(1, PlayerAction::Attack { column: 1 }).unwrap();
+        assert_eq!((engine.state.player2.shield, engine.state.player2.life), (0, 20));
+    }
+
+    #[test]
+    fn counter_strike_damages_the_opposing_creature_in_its_lane() {
+        let mut engine = bare_game(GamePhase::Placement);
+        place_feign(&mut engine, 1, COUNTER_STRIKE, 2);
+        place_creature(&mut engine, 2, HOLY_KNIGHT, 2);
+        place_creature(&mut engine, 2, ANCIENT_TREANT, 1);
+
+        engine.process_action(1, PlayerAction::RevealFeign { column: 2 }).unwrap();
+
+        assert!(creature(&engine, 2, 2).is_none());
+        assert_eq!(stats(creature(&engine, 2, 1)), (4, 6));
+    }
+
+    #[test]
+    fn mana_boost_grants_mana() {
+        let mut engine = bare_game(GamePhase::Placement);
+        place_feign(&mut engine, 1, MANA_BOOST, 0);
+
+        engine.process_action(1, PlayerAction::RevealFeign { column: 0 }).unwrap();
+
+        assert_eq!(engine.state.player1.mana, 7);
+        assert!(matches!(engine.state.events.last(), Some(GameEvent::ManaGained { player_id: 1, amount: 2 })));
+    }
+
+    #[test]
+    fn illusion_summons_a_token_that_fades() {
+        let mut engine = bare_game(GamePhase::Placement);
+        place_feign(&mut engine, 1, ILLUSION, 1);
+        place_creature(&mut engine, 1, FOREST_WOLF, 0);
+
+        engine.process_action(1, PlayerAction::RevealFeign { column: 1 }).unwrap();
+        let token = creature(&engine, 1, 1).unwrap();
+        assert_eq!((token.card.id, token.current_attack, token.current_defense), (0, 2, 2));
+        assert_eq!(token.remaining_turns, Some(2));
+
+        // Lasts through the opponent's turn, then fades; the revealed feign is discarded
+        end_turn(&mut engine);
+        assert!(creature(&engine, 1, 1).is_some());
+        assert!(engine.state.player1.board.feigns[1].is_none());
+        end_turn(&mut engine);
+        assert!(creature(&engine, 1, 1).is_none());
+        assert!(engine.state.events.iter().any(|event| matches!(event, GameEvent::CreatureFaded { player_id: 1, .. })));
+        assert_eq!(stats(creature(&engine, 1, 0)), (3, 2));
+    }
+
+    #[test]
+    fn illusion_takes_the_first_open_column_when_its_own_is_taken() {
+        let mut engine = bare_game(GamePhase::Placement);
+        place_feign(&mut engine, 1, ILLUSION, 0);
+        place_creature(&mut engine, 1, FOREST_WOLF, 0);
+
+        engine.process_action(1, PlayerAction::RevealFeign { column: 0 }).unwrap();
+
+        assert_eq!(creature(&engine, 1, 1).unwrap().card.name, "Illusion");
+    }
+
+    #[test]
+    fn soul_drain_weakens_every_enemy_creature() {
+        let mut engine = bare_game(GamePhase::Placement);
+        place_feign(&mut engine, 1, SOUL_DRAIN, 3);
+        place_creature(&mut engine, 1, FOREST_WOLF, 0);
+        place_creature(&mut engine, 2, VINE_SPRITE, 0);
+        place_creature(&mut engine, 2, HOLY_KNIGHT, 1);
+
+        engine.process_action(1, PlayerAction::RevealFeign { column: 3 }).unwrap();
+
+        assert!(creature(&engine, 2, 0).is_none());
+        assert_eq!(stats(creature(&engine, 2, 1)), (2, 2));
+        assert_eq!(stats(creature(&engine, 1, 0)), (3, 2));
+    }
+
+    #[test]
+    fn arcane_resonance_is_stronger_under_a_global_effect() {
+        let mut engine = bare_game(GamePhase::Placement);
+        place_feign(&mut engine, 1, ARCANE_RESONANCE, 0);
+        place_creature(&mut engine, 1, FOREST_WOLF, 0);
+        place_creature(&mut engine, 2, HOLY_KNIGHT, 0);
+
+        let mut without = engine.clone();
+        without.process_action(1, PlayerAction::RevealFeign { column: 0 }).unwrap();
+        assert_eq!(stats(creature(&without, 1, 0)), (4, 3));
+
+        let blessing = give_card(&mut engine, 1, BLESSING_OF_GROWTH);
+        engine.process_action(1, PlayerAction::PlayEffect { instance_id: blessing }).unwrap();
+        engine.process_action(1, PlayerAction::RevealFeign { column: 0 }).unwrap();
+        // +2/+2 from the feign on top of the blessing's +1/+1; the enemy only gets the blessing
+        assert_eq!(stats(creature(&engine, 1, 0)), (6, 5));
+        assert_eq!(stats(creature(&engine, 2, 0)), (4, 4));
+    }
+
+    /// Have player 1 play an effect card from their hand
+    fn play_effect(engine: &mut GameEngine, card_id: u32) {
+        let instance_id = give_card(engine, 1, card_id);
+        engine.process_action(1, PlayerAction::PlayEffect { instance_id }).unwrap();
+    }
+
+    #[test]
+    fn stat_modifiers_last_as_long_as_the_effect() {
+        let mut engine = bare_game(GamePhase::Placement);
+        place_creature(&mut engine, 1, FOREST_WOLF, 0);
+        place_creature(&mut engine, 2, HOLY_KNIGHT, 0);
+
+        play_effect(&mut engine, BLESSING_OF_GROWTH);
+        assert_eq!(stats(creature(&engine, 1, 0)), (4, 3));
+        assert_eq!(stats(creature(&engine, 2, 0)), (4, 4));
+
+        // Creatures played while it is active get the bonus too
+        let sprite = give_card(&mut engine, 1, VINE_SPRITE);
+        engine.process_action(1, PlayerAction::PlayCreature { instance_id: sprite, column: 1 }).unwrap();
+        assert_eq!(stats(creature(&engine, 1, 1)), (2, 2));
+
+        // It ticks down at the end of every turn, whoever's turn it was
+        end_turn(&mut engine);
+        end_turn(&mut engine);
+        assert_eq!(engine.state.global_effect.as_ref().unwrap().remaining_duration, 1);
+        assert_eq!(stats(creature(&engine, 1, 0)), (4, 3));
+        end_turn(&mut engine);
+        assert!(engine.state.global_effect.is_none());
+        assert!(engine.state.events.iter().any(|event| matches!(event, GameEvent::EffectExpired { .. })));
+        assert_eq!(stats(creature(&engine, 1, 0)), (3, 2));
+        assert_eq!(stats(creature(&engine, 1, 1)), (1, 1));
+    }
+
+    #[test]
+    fn colored_stat_modifiers_only_touch_their_color() {
+        let mut engine = bare_game(GamePhase::Placement);
+        place_creature(&mut engine, 1, MYSTIC_SCHOLAR, 0);
+        place_creature(&mut engine, 2, FOREST_WOLF, 0);
+
+        play_effect(&mut engine, ARCANE_AMPLIFICATION);
+
+        assert_eq!(stats(creature(&engine, 1, 0)), (3, 5));
+        assert_eq!(stats(creature(&engine, 2, 0)), (3, 2));
+    }
+
+    #[test]
+    fn stat_penalties_stop_at_zero() {
+        let mut engine = bare_game(GamePhase::Placement);
+        place_creature(&mut engine, 1, VINE_SPRITE, 0);
+
+        play_effect(&mut engine, CURSE_OF_WEAKNESS);
+
+        assert_eq!(stats(creature(&engine, 1, 0)), (0, 1));
+    }
+
+    #[test]
+    fn playing_an_effect_replaces_the_active_one() {
+        let mut engine = bare_game(GamePhase::Placement);
+        place_creature(&mut engine, 1, FOREST_WOLF, 0);
+
+        play_effect(&mut engine, BLESSING_OF_GROWTH);
+        play_effect(&mut engine, CURSE_OF_WEAKNESS);
+
+        assert_eq!(engine.state.global_effect.as_ref().unwrap().card.id, CURSE_OF_WEAKNESS);
+        assert!(engine.state.events.iter().any(|event| matches!(event, GameEvent::EffectReplaced { .. })));
+        assert_eq!(stats(creature(&engine, 1, 0)), (2, 2));
+    }
+
+    #[test]
+    fn creatures_take_damage_at_the_end_of_each_turn() {
+        let mut engine = bare_game(GamePhase::Placement);
+        place_creature(&mut engine, 1, FOREST_WOLF, 0);
+        place_creature(&mut engine, 2, VINE_SPRITE, 1);
+
+        play_effect(&mut engine, INFERNO);
+        assert_eq!(stats(creature(&engine, 1, 0)), (3, 2));
+
+        end_turn(&mut engine);
+        assert!(creature(&engine, 2, 1).is_none());
+        assert_eq!(stats(creature(&engine, 1, 0)), (3, 1));
+        end_turn(&mut engine);
+        assert!(creature(&engine, 1, 0).is_none());
+        assert!(engine.state.global_effect.is_none());
+    }
+
+    #[test]
+    fn extra_draws_apply_to_the_draw_phase() {
+        let mut engine = bare_game(GamePhase::Placement);
+        play_effect(&mut engine, FROZEN_TIME);
+        end_turn(&mut engine);
+
+        engine.process_action(2, PlayerAction::EndPhase).unwrap();
+
+        assert_eq!(engine.state.player2.hand.len(), 2);
+    }
+
+    #[test]
+    fn damage_reduction_lowers_combat_and_feign_damage() {
+        let mut engine = bare_game(GamePhase::Placement);
+        place_creature(&mut engine, 1, FOREST_WOLF, 0);
+        place_creature(&mut engine, 2, ANCIENT_TREANT, 1);
+        place_feign(&mut engine, 1, COUNTER_STRIKE, 1);
+
+        play_effect(&mut engine, DIVINE_PROTECTION);
+        engine.process_action(1, PlayerAction::RevealFeign { column: 1 }).unwrap();
+        assert_eq!(stats(creature(&engine, 2, 1)), (4, 4));
+
+        engine.process_action(1, PlayerAction::EndPhase).unwrap();
+        engine.process_action(1, PlayerAction::Attack { column: 0 }).unwrap();
+        assert_eq!(engine.state.player2.life, 18);
+    }
+}
@@ -0,0 +1,1095 @@
+use crate::models::*;
+use crate::core::combat::CombatResolver;
+use crate::core::effects::EffectResolver;
+use crate::core::catalog::CardCatalog;
+use crate::core::replay::{Replay, ReplayAction};
+use crate::core::save::EngineSnapshot;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+
+/// Main game engine that manages game state and turn flow
+#[derive(Debug, Clone)]
+pub struct GameEngine {
+    pub state: GameState,
+    rng: ChaCha12Rng, // Seeded from `state.seed`; every random choice in a game comes from here
+    recording: Option<Replay>, // Only games dealt by the engine can be replayed
+    undo_stack: Vec<UndoEntry>,
+    redo_stack: Vec<(u32, PlayerAction)>,
+    undo_enabled: bool, // Off for games nobody takes actions back in, such as AI playouts
+}
+
+/// The engine before an undoable action, so the action can be taken back. The
+/// event log is left out of the state, since actions only ever append to it.
+#[derive(Debug, Clone)]
+struct UndoEntry {
+    state: GameState,
+    events_len: usize,
+    rng_position: u128,
+    player_id: u32,
+    action: PlayerAction,
+}
+
+impl GameEngine {
+    /// Create a new game with two players using the bundled card catalog and a random seed
+    pub fn new(player1_name: String, player2_name: String) -> Self {
+        Self::with_catalog(player1_name, player2_name, CardCatalog::bundled(), None)
+    }
+
+    /// Create a new game with two players, building mock decks from a catalog
+    pub fn with_catalog(player1_name: String, player2_name: String, catalog: &CardCatalog, seed: Option<u64>) -> Self {
+        Self::with_decks(
+            player1_name,
+            crate::core::create_mock_deck(catalog),
+            player2_name,
+            crate::core::create_mock_deck(catalog),
+            seed,
+        )
+    }
+
+    /// Create a new game where each player draws from the given deck. Decks are
+    /// shuffled from `seed` (a fresh one if None), so the same seed and decks
+    /// always deal the same game.
+    pub fn with_decks(
+        player1_name: String,
+        player1_deck: Vec<Card>,
+        player2_name: String,
+        player2_deck: Vec<Card>,
+        seed: Option<u64>,
+    ) -> Self {
+        let seed = seed.unwrap_or_else(rand::random);
+        let recording = Replay::new(
+            seed,
+            player1_name.clone(),
+            player1_deck.clone(),
+            player2_name.clone(),
+            player2_deck.clone(),
+        );
+        let mut engine = GameEngine::from_state(GameState {
+            player1: Player {
+                id: 1,
+                name: player1_name,
+                life: 20,
+                mana: 5, // Start with 5 mana
+                shield: 0,
+                hand: Vec::new(),
+                deck: Vec::new(),
+                board: PlayerBoard::new(),
+            },
+            player2: Player {
+                id: 2,
+                name: player2_name,
+                life: 20,
+                mana: 5, // Start with 5 mana
+                shield: 0,
+                hand: Vec::new(),
+                deck: Vec::new(),
+                board: PlayerBoard::new(),
+            },
+            current_player: 1,
+            turn_number: 1,
+            phase: GamePhase::Draw,
+            global_effect: None,
+            pending_attack: None,
+            instance_ids: InstanceIdAllocator::default(),
+            seed,
+            events: vec![GameEvent::GameStarted],
+        });
+
+        // Shuffle before handing out instance ids, so an id says nothing about
+        // where the card sat in the deck list
+        let (mut player1_deck, mut player2_deck) = (player1_deck, player2_deck);
+        player1_deck.shuffle(&mut engine.rng);
+        player2_deck.shuffle(&mut engine.rng);
+        engine.state.player1.deck = engine.instantiate_deck(player1_deck);
+        engine.state.player2.deck = engine.instantiate_deck(player2_deck);
+        engine.recording = Some(recording);
+
+        // Draw initial hands (5 cards each)
+        for _ in 0..5 {
+            engine.draw_card(1);
+            engine.draw_card(2);
+        }
+
+        engine
+    }
+
+    /// Wrap an existing state, seeding the random stream from its recorded seed.
+    /// Actions are not recorded, since the deal that led here is unknown.
+    pub fn from_state(state: GameState) -> Self {
+        GameEngine {
+            rng: ChaCha12Rng::seed_from_u64(state.seed),
+            state,
+            recording: None,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            undo_enabled: true,
+        }
+    }
+
+    /// Stop keeping undo history, for games where no one takes actions back
+    /// and the snapshots would only cost time
+    pub fn disable_undo(&mut self) {
+        self.undo_enabled = false;
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+
+    /// Stop recording the replay, for games the engine did not deal in full,
+    /// such as network games where the guest's cards stay hidden
+    pub fn disable_recording(&mut self) {
+        self.recording = None;
+    }
+
+    /// Capture everything needed to resume this exact game later
+    pub fn snapshot(&self) -> EngineSnapshot {
+        EngineSnapshot {
+            state: self.state.clone(),
+            rng_position: self.rng.get_word_pos(),
+            replay: self.recording.clone(),
+        }
+    }
+
+    /// Resume a game from a snapshot, with its random stream where it left off
+    pub fn restore(snapshot: EngineSnapshot) -> Self {
+        let mut engine = GameEngine::from_state(snapshot.state);
+        engine.rng.set_word_pos(snapshot.rng_position);
+        engine.recording = snapshot.replay;
+        engine
+    }
+
+    /// The replay of this game so far, if it is being recorded
+    pub fn replay(&self) -> Option<&Replay> {
+        self.recording.as_ref()
+    }
+
+    /// Give every card in a deck list its own game-unique instance id
+    fn instantiate_deck(&mut self, cards: Vec<Card>) -> Vec<CardInstance> {
+        cards.into_iter()
+            .map(|card| CardInstance {
+                instance_id: self.state.instance_ids.allocate(),
+                card,
+            })
+            .collect()
+    }
+
+    /// Process a player action, recording it for the replay if it is accepted
+    pub fn process_action(&mut self, player_id: u32, action: PlayerAction) -> Result<ActionResult, GameError> {
+        let result = self.perform_action(player_id, action)?;
+        self.redo_stack.clear();
+        Ok(result)
+    }
+
+    /// Apply an action and keep the replay and undo history up to date
+    fn perform_action(&mut self, player_id: u32, action: PlayerAction) -> Result<ActionResult, GameError> {
+        // Anything that shows a player new information, or hands a decision
+        // to the opponent, can't be taken back
+        let undoable = self.undo_enabled
+            && self.state.pending_attack.is_none()
+            && match action {
+                PlayerAction::PlayCreature { .. }
+                | PlayerAction::PlayFeign { .. }
+                | PlayerAction::PlayEffect { .. }
+                | PlayerAction::Attack { .. } => true,
+                PlayerAction::EndPhase => matches!(self.state.phase, GamePhase::Placement | GamePhase::Attack),
+                PlayerAction::RevealFeign { .. } | PlayerAction::PassResponse => false,
+            };
+        let before = undoable.then(|| self.undo_entry(player_id, &action));
+        let (turn, phase) = (self.state.turn_number, self.state.phase.clone());
+
+        let result = self.apply_action(player_id, action.clone())?;
+
+        if let Some(recording) = self.recording.as_mut() {
+            recording.actions.push(ReplayAction { player_id, turn, phase, action });
+        }
+
+        // An attack the defender may answer can't be taken back either
+        match before {
+            Some(entry) if self.state.pending_attack.is_none() => self.undo_stack.push(entry),
+            _ => self.undo_stack.clear(),
+        }
+        Ok(result)
+    }
+
+    /// Snapshot the engine before `player_id` takes `action`
+    fn undo_entry(&mut self, player_id: u32, action: &PlayerAction) -> UndoEntry {
+        let events = std::mem::take(&mut self.state.events);
+        let state = self.state.clone();
+        self.state.events = events;
+        UndoEntry {
+            state,
+            events_len: self.state.events.len(),
+            rng_position: self.rng.get_word_pos(),
+            player_id,
+            action: action.clone(),
+        }
+    }
+
+    /// Whether `player_id` can take back their last action
+    pub fn can_undo(&self, player_id: u32) -> bool {
+        self.undo_stack.last().is_some_and(|entry| entry.player_id == player_id)
+    }
+
+    /// Whether `player_id` can repeat an action they took back
+    pub fn can_redo(&self, player_id: u32) -> bool {
+        self.redo_stack.last().is_some_and(|(redo_player, _)| *redo_player == player_id)
+    }
+
+    /// Whether `player_id` can undo or redo right now
+    pub fn undo_availability(&self, player_id: u32) -> UndoAvailability {
+        UndoAvailability { can_undo: self.can_undo(player_id), can_redo: self.can_redo(player_id) }
+    }
+
+    /// Take back the last action of the current turn. Only possible until the
+    /// player draws, reveals a feign, gives the opponent a chance to respond or
+    /// ends their turn.
+    pub fn undo(&mut self, player_id: u32) -> Result<ActionResult, GameError> {
+        if !self.can_undo(player_id) {
+            return Err(GameError::NothingToUndo);
+        }
+
+        let entry = self.undo_stack.pop().expect("can_undo checked the stack");
+        let mut events = std::mem::take(&mut self.state.events);
+        events.truncate(entry.events_len);
+        self.state = entry.state;
+        self.state.events = events;
+        self.rng.set_word_pos(entry.rng_position);
+        if let Some(recording) = self.recording.as_mut() {
+            recording.actions.pop();
+        }
+        self.redo_stack.push((entry.player_id, entry.action));
+
+        Ok(ActionResult {
+            message: "Action undone".to_string(),
+            new_state: self.state.clone(),
+        })
+    }
+
+    /// Repeat the last undone action
+    pub fn redo(&mut self, player_id: u32) -> Result<ActionResult, GameError> {
+        if !self.can_redo(player_id) {
+            return Err(GameError::NothingToRedo);
+        }
+
+        let (player_id, action) = self.redo_stack.pop().expect("can_redo checked the stack");
+        self.perform_action(player_id, action)
+    }
+
+    /// Validate and carry out a player action
+    fn apply_action(&mut self, player_id: u32, action: PlayerAction) -> Result<ActionResult, GameError> {
+        if let Some(winner) = self.is_game_over() {
+            return Err(GameError::GameOver { winner });
+        }
+
+        // A declared attack pauses the turn until the defender responds
+        if let Some(pending) = self.state.pending_attack.clone() {
+            return self.respond_to_attack(player_id, pending, action);
+        }
+
+        if player_id != self.state.current_player {
+            return Err(GameError::NotYourTurn);
+        }
+
+        match action {
+            PlayerAction::PlayCreature { instance_id, column } => {
+                self.play_creature(player_id, instance_id, column)
+            }
+            PlayerAction::PlayFeign { instance_id, column } => {
+                self.play_feign(player_id, instance_id, column)
+            }
+            PlayerAction::PlayEffect { instance_id } => {
+                self.play_effect(player_id, instance_id)
+            }
+            PlayerAction::Attack { column } => {
+                self.attack(player_id, column)
+            }
+            PlayerAction::RevealFeign { column } => {
+                self.reveal_feign(player_id, column, column)
+            }
+            PlayerAction::PassResponse => Err(GameError::NoPendingAttack),
+            PlayerAction::EndPhase => {
+                self.end_phase()
+            }
+        }
+    }
+
+    /// Every action `process_action` would accept from `player_id` right now
+    pub fn legal_actions(&self, player_id: u32) -> Vec<PlayerAction> {
+        if self.is_game_over().is_some() {
+            return Vec::new();
+        }
+        let player = if player_id == 1 {
+            &self.state.player1
+        } else {
+            &self.state.player2
+        };
+        let hidden_feigns = || (0..BOARD_COLUMNS)
+            .filter(|&column| player.board.feigns[column].as_ref().is_some_and(|feign| !feign.is_revealed));
+
+        // While an attack is pending only the defender may act
+        if let Some(pending) = &self.state.pending_attack {
+            if player_id != pending.defending_player() {
+                return Vec::new();
+            }
+            let mut actions: Vec<PlayerAction> = hidden_feigns()
+                .map(|column| PlayerAction::RevealFeign { column })
+                .collect();
+            actions.push(PlayerAction::PassResponse);
+            return actions;
+        }
+
+        if player_id != self.state.current_player {
+            return Vec::new();
+        }
+
+        let mut actions = Vec::new();
+        match self.state.phase {
+            GamePhase::Placement => {
+                for card in player.hand.iter().filter(|card| card.mana_cost <= player.mana) {
+                    match card.card_type {
+                        CardType::Creature => {
+                            for column in (0..BOARD_COLUMNS).filter(|&c| player.board.creatures[c].is_none()) {
+                                actions.push(PlayerAction::PlayCreature { instance_id: card.instance_id, column });
+                            }
+                        }
+                        CardType::Feign => {
+                            for column in (0..BOARD_COLUMNS).filter(|&c| player.board.feigns[c].is_none()) {
+                                actions.push(PlayerAction::PlayFeign { instance_id: card.instance_id, column });
+                            }
+                        }
+                        CardType::Effect => {
+                            actions.push(PlayerAction::PlayEffect { instance_id: card.instance_id });
+                        }
+                    }
+                }
+            }
+            GamePhase::Attack => {
+                for column in 0..BOARD_COLUMNS {
+                    if player.board.creatures[column].as_ref().is_some_and(|creature| !creature.is_tapped) {
+                        actions.push(PlayerAction::Attack { column });
+                    }
+                }
+            }
+            GamePhase::Draw | GamePhase::EndTurn => {}
+        }
+
+        // A player may spring their own feigns at any point of their turn
+        actions.extend(hidden_feigns().map(|column| PlayerAction::RevealFeign { column }));
+        actions.push(PlayerAction::EndPhase);
+        actions
+    }
+
+    /// Draw a card for the specified player
+    pub fn draw_card(&mut self, player_id: u32) -> bool {
+        let player = if player_id == 1 {
+            &mut self.state.player1
+        } else {
+            &mut self.state.player2
+        };
+
+        match player.deck.pop() {
+            Some(card) => {
+                let instance_id = card.instance_id;
+                player.hand.push(card);
+                self.emit(GameEvent::CardDrawn { player_id, instance_id: Some(instance_id) });
+                true
+            }
+            None => {
+                self.emit(GameEvent::DeckEmpty { player_id });
+                false
+            }
+        }
+    }
+
+    /// Check that the game is in `expected` phase
+    fn require_phase(&self, expected: GamePhase) -> Result<(), GameError> {
+        if self.state.phase != expected {
+            return Err(GameError::WrongPhase { expected, actual: self.state.phase.clone() });
+        }
+        Ok(())
+    }
+
+    /// Take a card of the given type from a player's hand, paying its mana cost
+    fn pay_for_card(&mut self, player_id: u32, instance_id: u32, card_type: CardType) -> Result<CardInstance, GameError> {
+        let player = if player_id == 1 {
+            &mut self.state.player1
+        } else {
+            &mut self.state.player2
+        };
+
+        let card_index = player.hand.iter()
+            .position(|c| c.instance_id == instance_id)
+            .ok_or(GameError::CardNotInHand { instance_id })?;
+        let card = &player.hand[card_index];
+
+        if card.card_type != card_type {
+            return Err(GameError::WrongCardType { expected: card_type, actual: card.card_type.clone() });
+        }
+        if card.mana_cost > player.mana {
+            return Err(GameError::NotEnoughMana { required: card.mana_cost, available: player.mana });
+        }
+
+        player.mana -= card.mana_cost;
+        Ok(player.hand.remove(card_index))
+    }
+
+    /// Play a creature card into a column of the front row
+    fn play_creature(&mut self, player_id: u32, instance_id: u32, column: usize) -> Result<ActionResult, GameError> {
+        self.require_phase(GamePhase::Placement)?;
+        if column >= BOARD_COLUMNS {
+            return Err(GameError::InvalidIndex { index: column, max: BOARD_COLUMNS - 1 });
+        }
+
+        let occupied = if player_id == 1 {
+            self.state.player1.board.creatures[column].is_some()
+        } else {
+            self.state.player2.board.creatures[column].is_some()
+        };
+        if occupied {
+            return Err(GameError::ColumnOccupied { column });
+        }
+
+        let card = self.pay_for_card(player_id, instance_id, CardType::Creature)?;
+        let player = if player_id == 1 {
+            &mut self.state.player1
+        } else {
+            &mut self.state.player2
+        };
+        player.board.creatures[column] = Some(Creature::new(card.clone()));
+
+        self.emit(GameEvent::CreaturePlayed { player_id, card, column });
+        self.refresh_creature_stats();
+
+        Ok(ActionResult {
+            message: "Creature played successfully".to_string(),
+            new_state: self.state.clone(),
+        })
+    }
+
+    /// Play a feign card face-down into a column of the back row
+    fn play_feign(&mut self, player_id: u32, instance_id: u32, column: usize) -> Result<ActionResult, GameError> {
+        self.require_phase(GamePhase::Placement)?;
+        if column >= BOARD_COLUMNS {
+            return Err(GameError::InvalidIndex { index: column, max: BOARD_COLUMNS - 1 });
+        }
+
+        let occupied = if player_id == 1 {
+            self.state.player1.board.feigns[column].is_some()
+        } else {
+            self.state.player2.board.feigns[column].is_some()
+        };
+        if occupied {
+            return Err(GameError::ColumnOccupied { column });
+        }
+
+        let card = self.pay_for_card(player_id, instance_id, CardType::Feign)?;
+        let player = if player_id == 1 {
+            &mut self.state.player1
+        } else {
+            &mut self.state.player2
+        };
+        player.board.feigns[column] = Some(FeignCard {
+            card: card.clone(),
+            is_revealed: false,
+        });
+
+        self.emit(GameEvent::FeignPlayed { player_id, card: Some(card), column });
+
+        Ok(ActionResult {
+            message: "Feign played successfully".to_string(),
+            new_state: self.state.clone(),
+        })
+    }
+
+    /// Play an effect card
+    fn play_effect(&mut self, player_id: u32, instance_id: u32) -> Result<ActionResult, GameError> {
+        self.require_phase(GamePhase::Placement)?;
+
+        let card = self.pay_for_card(player_id, instance_id, CardType::Effect)?;
+        // Place effect (replaces existing global effect)
+        let effect = GlobalEffect {
+            remaining_duration: card.duration.unwrap_or(3),
+            card: card.clone(),
+        };
+        let replaced = self.state.global_effect.replace(effect);
+        self.emit(GameEvent::EffectPlayed { player_id, card });
+        if let Some(replaced) = replaced {
+            self.emit(GameEvent::EffectReplaced { name: replaced.card.name.clone() });
+        }
+        self.refresh_creature_stats();
+
+        Ok(ActionResult {
+            message: "Effect played successfully".to_string(),
+            new_state: self.state.clone(),
+        })
+    }
+
+    /// Attack with the creature in a column, fighting the opposing creature in that lane
+    fn attack(&mut self, player_id: u32, column: usize) -> Result<ActionResult, GameError> {
+        self.require_phase(GamePhase::Attack)?;
+
+        let attacker = {
+            let player = if player_id == 1 {
+                &mut self.state.player1
+            } else {
+                &mut self.state.player2
+            };
+
+            player.board.creatures.get_mut(column).and_then(|slot| slot.as_mut())
+        };
+
+        match attacker {
+            None => return Err(GameError::NoCreature { column }),
+            Some(creature) if creature.is_tapped => {
+                return Err(GameError::CreatureAlreadyAttacked { column });
+            }
+            Some(creature) => creature.is_tapped = true,
+        }
+
+        // Give the defender a chance to answer with a face-down feign
+        let (defender_name, defender_can_respond) = {
+            let defender = if player_id == 1 {
+                &self.state.player2
+            } else {
+                &self.state.player1
+            };
+            let can_respond = defender.board.feigns.iter().flatten().any(|feign| !feign.is_revealed);
+            (defender.name.clone(), can_respond)
+        };
+
+        if !defender_can_respond {
+            return Ok(self.resolve_attack(player_id, column));
+        }
+
+        self.state.pending_attack = Some(PendingAttack {
+            attacking_player: player_id,
+            column,
+        });
+        self.emit(GameEvent::AttackDeclared { player_id, column });
+
+        Ok(ActionResult {
+            message: format!("Attack declared - waiting for {} to respond", defender_name),
+            new_state: self.state.clone(),
+        })
+    }
+
+    /// Handle the defending player's answer to a pending attack
+    fn respond_to_attack(&mut self, player_id: u32, pending: PendingAttack, action: PlayerAction) -> Result<ActionResult, GameError> {
+        if player_id != pending.defending_player() {
+            return Err(GameError::AwaitingResponse);
+        }
+
+        match action {
+            PlayerAction::RevealFeign { column } => {
+                // A feign revealed in response acts on the attacked lane
+                self.reveal_feign(player_id, column, pending.column)?;
+            }
+            PlayerAction::PassResponse => {
+                self.emit(GameEvent::ResponsePassed { player_id });
+            }
+            _ => return Err(GameError::InvalidResponse),
+        }
+
+        self.state.pending_attack = None;
+        Ok(self.resolve_attack(pending.attacking_player, pending.column))
+    }
+
+    /// Resolve combat for an attack once it can no longer be answered
+    fn resolve_attack(&mut self, player_id: u32, column: usize) -> ActionResult {
+        let attacker_survived = {
+            let player = if player_id == 1 {
+                &self.state.player1
+            } else {
+                &self.state.player2
+            };
+            player.board.creatures[column].is_some()
+        };
+
+        if !attacker_survived {
+            self.emit(GameEvent::AttackStopped { player_id, column });
+            return ActionResult {
+                message: "Attack was stopped".to_string(),
+                new_state: self.state.clone(),
+            };
+        }
+
+        let combat = CombatResolver::resolve_combat(&mut self.state, player_id, column);
+        for event in combat.events {
+            self.emit(event);
+        }
+
+        ActionResult {
+            message: combat.summary,
+            new_state: self.state.clone(),
+        }
+    }
+
+    /// Reveal the feign card in a column, resolving its effects against `target_column`
+    fn reveal_feign(&mut self, player_id: u32, column: usize, target_column: usize) -> Result<ActionResult, GameError> {
+        let card_name = {
+            let player = if player_id == 1 {
+                &self.state.player1
+            } else {
+                &self.state.player2
+            };
+
+            match player.board.feigns.get(column).and_then(|slot| slot.as_ref()) {
+                None => return Err(GameError::NoFeign { column }),
+                Some(feign) if feign.is_revealed => return Err(GameError::FeignAlreadyRevealed { column }),
+                Some(feign) => feign.card.name.clone(),
+            }
+        };
+
+        for event in CombatResolver::apply_feign_effects(&mut self.state, player_id, column, target_column) {
+            self.emit(event);
+        }
+
+        Ok(ActionResult {
+            message: format!("Revealed: {}", card_name),
+            new_state: self.state.clone(),
+        })
+    }
+
+    /// End the current phase and advance to next
+    fn end_phase(&mut self) -> Result<ActionResult, GameError> {
+        match self.state.phase {
+            GamePhase::Draw => {
+                // Draw phase: draw a card (plus any granted by global effects) and gain mana
+                for _ in 0..=EffectResolver::extra_draws(&self.state) {
+                    self.draw_card(self.state.current_player);
+                }
+                let player = if self.state.current_player == 1 {
+                    &mut self.state.player1
+                } else {
+                    &mut self.state.player2
+                };
+                player.mana += 2; // Gain 2 mana per turn
+                self.state.phase = GamePhase::Placement;
+                self.emit(GameEvent::PhaseChanged { phase: GamePhase::Placement });
+            }
+            GamePhase::Placement => {
+                self.state.phase = GamePhase::Attack;
+                self.emit(GameEvent::PhaseChanged { phase: GamePhase::Attack });
+            }
+            GamePhase::Attack => {
+                self.state.phase = GamePhase::EndTurn;
+                self.emit(GameEvent::PhaseChanged { phase: GamePhase::EndTurn });
+            }
+            GamePhase::EndTurn => {
+                // End turn: apply global effect triggers, tick them down, switch players
+                for event in EffectResolver::apply_end_of_turn_triggers(&mut self.state) {
+                    self.emit(event);
+                }
+
+                let expired_effect = if let Some(ref mut effect) = self.state.global_effect {
+                    effect.remaining_duration -= 1;
+                    if effect.remaining_duration == 0 {
+                        let effect_name = effect.card.name.clone();
+                        self.state.global_effect = None;
+                        Some(effect_name)
+                    } else {
+                        None
+                    }
+                } else {
+                    None
+                };
+
+                if let Some(effect_name) = expired_effect {
+                    self.emit(GameEvent::EffectExpired { name: effect_name });
+                    self.refresh_creature_stats();
+                }
+
+                // Temporary creatures fade and revealed feigns are discarded
+                let mut faded = Vec::new();
+                for player in [&mut self.state.player1, &mut self.state.player2] {
+                    for slot in player.board.creatures.iter_mut() {
+                        if let Some(creature) = slot {
+                            if let Some(turns) = creature.remaining_turns.as_mut() {
+                                *turns = turns.saturating_sub(1);
+                                if *turns == 0 {
+                                    faded.push(GameEvent::CreatureFaded {
+                                        player_id: player.id,
+                                        instance_id: creature.card.instance_id,
+                                        name: creature.card.name.clone(),
+                                    });
+                                    *slot = None;
+                                }
+                            }
+                        }
+                    }
+                    for slot in player.board.feigns.iter_mut() {
+                        if slot.as_ref().is_some_and(|feign| feign.is_revealed) {
+                            *slot = None;
+                        }
+                    }
+                }
+                for event in faded {
+                    self.emit(event);
+                }
+
+                // Switch to other player and ready their creatures
+                self.state.current_player = if self.state.current_player == 1 { 2 } else { 1 };
+                self.state.turn_number += 1;
+                self.state.phase = GamePhase::Draw;
+
+                let next_player = if self.state.current_player == 1 {
+                    &mut self.state.player1
+                } else {
+                    &mut self.state.player2
+                };
+                for creature in next_player.board.creatures.iter_mut().flatten() {
+                    creature.is_tapped = false;
+                }
+
+                self.emit(GameEvent::TurnStarted {
+                    turn: self.state.turn_number,
+                    player_id: self.state.current_player,
+                });
+            }
+        }
+
+        Ok(ActionResult {
+            message: format!("Phase advanced to {:?}", self.state.phase),
+            new_state: self.state.clone(),
+        })
+    }
+
+    /// Check if the game is over
+    pub fn is_game_over(&self) -> Option<u32> {
+        if self.state.player1.life == 0 {
+            Some(2) // Player 2 wins
+        } else if self.state.player2.life == 0 {
+            Some(1) // Player 1 wins
+        } else {
+            None // Game continues
+        }
+    }
+
+    /// Recompute creature stats after the active global effect or the board changed
+    fn refresh_creature_stats(&mut self) {
+        for event in EffectResolver::refresh_creature_stats(&mut self.state) {
+            self.emit(event);
+        }
+    }
+
+    /// Record something that happened in the game
+    fn emit(&mut self, event: GameEvent) {
+        self.state.events.push(event);
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use rand::Rng;
+
+    pub(crate) fn new_game(seed: u64) -> GameEngine {
+        GameEngine::with_catalog("Alice".to_string(), "Bob".to_string(), CardCatalog::bundled(), Some(seed))
+    }
+
+    /// Play up to `max_actions` random legal actions, chosen from `seed`
+    pub(crate) fn play_random(engine: &mut GameEngine, seed: u64, max_actions: usize) {
+        let mut rng = ChaCha12Rng::seed_from_u64(seed);
+        for _ in 0..max_actions {
+            if engine.is_game_over().is_some() {
+                break;
+            }
+            let player_id = engine.state.acting_player();
+            let legal = engine.legal_actions(player_id);
+            let action = legal[rng.gen_range(0..legal.len())].clone();
+            engine.process_action(player_id, action).unwrap();
+        }
+    }
+
+    /// The state in a form that can be compared
+    pub(crate) fn state_json(engine: &GameEngine) -> serde_json::Value {
+        serde_json::to_value(&engine.state).unwrap()
+    }
+
+    /// A game at `phase` of player 1's first turn with empty hands and boards,
+    /// for tests that set out exactly the cards they need
+    pub(crate) fn bare_game(phase: GamePhase) -> GameEngine {
+        let mut engine = new_game(1);
+        engine.state.phase = phase;
+        engine.state.player1.hand.clear();
+        engine.state.player2.hand.clear();
+        engine
+    }
+
+    pub(crate) fn player_mut(engine: &mut GameEngine, player_id: u32) -> &mut Player {
+        if player_id == 1 { &mut engine.state.player1 } else { &mut engine.state.player2 }
+    }
+
+    /// A new copy of a bundled card with its own instance id
+    pub(crate) fn card_instance(engine: &mut GameEngine, card_id: u32) -> CardInstance {
+        let card = CardCatalog::bundled().get(card_id).expect("no such card").clone();
+        CardInstance { instance_id: engine.state.instance_ids.allocate(), card }
+    }
+
+    /// Put a copy of a bundled card into a player's hand, returning its instance id
+    pub(crate) fn give_card(engine: &mut GameEngine, player_id: u32, card_id: u32) -> u32 {
+        let card = card_instance(engine, card_id);
+        let instance_id = card.instance_id;
+        player_mut(engine, player_id).hand.push(card);
+        instance_id
+    }
+
+    /// Put a creature straight onto a player's board
+    pub(crate) fn place_creature(engine: &mut GameEngine, player_id: u32, card_id: u32, column: usize) {
+        let card = card_instance(engine, card_id);
+        player_mut(engine, player_id).board.creatures[column] = Some(Creature::new(card));
+        engine.refresh_creature_stats();
+    }
+
+    /// Put a feign face down onto a player's board
+    pub(crate) fn place_feign(engine: &mut GameEngine, player_id: u32, card_id: u32, column: usize) {
+        let card = card_instance(engine, card_id);
+        player_mut(engine, player_id).board.feigns[column] = Some(FeignCard { card, is_revealed: false });
+    }
+
+    /// The creature in a column of a player's board, if any
+    pub(crate) fn creature(engine: &GameEngine, player_id: u32, column: usize) -> Option<&Creature> {
+        let player = if player_id == 1 { &engine.state.player1 } else { &engine.state.player2 };
+        player.board.creatures[column].as_ref()
+    }
+
+    /// End phases until the current turn is over
+    pub(crate) fn end_turn(engine: &mut GameEngine) {
+        let turn = engine.state.turn_number;
+        while engine.state.turn_number == turn {
+            engine.process_action(engine.state.current_player, PlayerAction::EndPhase).unwrap();
+        }
+    }
+
+    #[test]
+    fn custom_decks_are_shuffled_from_the_seed() {
+        let deck = crate::core::create_mock_deck(CardCatalog::bundled());
+        let deal = |seed| {
+            let engine = GameEngine::with_decks("Alice".to_string(), deck.clone(), "Bob".to_string(), deck.clone(), Some(seed));
+            let player = &engine.state.player1;
+            player.deck.iter().chain(&player.hand).map(|card| card.id).collect::<Vec<_>>()
+        };
+
+        let dealt = deal(7);
+        assert_ne!(dealt, deck.iter().map(|card| card.id).collect::<Vec<_>>(), "deck was dealt in list order");
+        assert_eq!(dealt, deal(7));
+        assert_ne!(dealt, deal(8));
+    }
+
+    #[test]
+    fn instance_ids_do_not_follow_the_deck_list() {
+        // The mock deck is in catalog order, so ids given out in list order
+        // would tell the opponent which card each id is
+        let deck = crate::core::create_mock_deck(CardCatalog::bundled());
+        let cards_by_id = |seed| {
+            let engine = new_game(seed);
+            let player = &engine.state.player1;
+            let mut cards: Vec<_> = player.deck.iter().chain(&player.hand).collect();
+            cards.sort_by_key(|card| card.instance_id);
+            cards.iter().map(|card| card.id).collect::<Vec<_>>()
+        };
+
+        let list_order: Vec<_> = deck.iter().map(|card| card.id).collect();
+        assert_ne!(cards_by_id(7), list_order);
+        assert_ne!(cards_by_id(7), cards_by_id(8));
+    }
+
+    /// Every action a player could try: each card in their hand on each
+    /// column (and one past the last), and every column-based or plain action
+    fn candidate_actions(engine: &GameEngine, player_id: u32) -> Vec<PlayerAction> {
+        let mut actions = vec![PlayerAction::PassResponse, PlayerAction::EndPhase];
+        for column in 0..=BOARD_COLUMNS {
+            actions.push(PlayerAction::Attack { column });
+            actions.push(PlayerAction::RevealFeign { column });
+        }
+        let player = if player_id == 1 { &engine.state.player1 } else { &engine.state.player2 };
+        for card in &player.hand {
+            let instance_id = card.instance_id;
+            actions.push(PlayerAction::PlayEffect { instance_id });
+            for column in 0..=BOARD_COLUMNS {
+                actions.push(PlayerAction::PlayCreature { instance_id, column });
+                actions.push(PlayerAction::PlayFeign { instance_id, column });
+            }
+        }
+        actions
+    }
+
+    #[test]
+    fn legal_actions_match_process_action() {
+        for seed in 0..8 {
+            let mut engine = new_game(seed);
+            let mut rng = ChaCha12Rng::seed_from_u64(seed);
+
+            for _ in 0..400 {
+                for player_id in [1, 2] {
+                    let legal = engine.legal_actions(player_id);
+                    for action in candidate_actions(&engine, player_id) {
+                        let accepted = engine.clone().process_action(player_id, action.clone()).is_ok();
+                        assert_eq!(
+                            accepted,
+                            legal.contains(&action),
+                            "seed {}, turn {}: player {} {:?}",
+                            seed,
+                            engine.state.turn_number,
+                            player_id,
+                            action
+                        );
+                    }
+                }
+
+                if engine.is_game_over().is_some() {
+                    break;
+                }
+                let player_id = engine.state.acting_player();
+                let legal = engine.legal_actions(player_id);
+                let action = legal[rng.gen_range(0..legal.len())].clone();
+                engine.process_action(player_id, action).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn finished_games_accept_no_actions() {
+        let mut engine = new_game(1);
+        engine.state.player2.life = 0;
+
+        assert_eq!(engine.is_game_over(), Some(1));
+        assert!(engine.legal_actions(1).is_empty());
+        assert_eq!(engine.process_action(1, PlayerAction::EndPhase).unwrap_err(), GameError::GameOver { winner: 1 });
+    }
+
+    #[test]
+    fn seed_and_actions_reproduce_the_game() {
+        let mut original = new_game(3);
+        play_random(&mut original, 3, 300);
+        let actions = &original.replay().unwrap().actions;
+        assert!(!actions.is_empty());
+
+        let mut repeat = new_game(3);
+        for recorded in actions {
+            repeat.process_action(recorded.player_id, recorded.action.clone()).unwrap();
+        }
+        assert_eq!(state_json(&repeat), state_json(&original));
+        assert_eq!(repeat.rng.get_word_pos(), original.rng.get_word_pos());
+
+        let deal = |seed| new_game(seed).state.player1.deck.iter().map(|card| card.id).collect::<Vec<_>>();
+        assert_ne!(deal(4), deal(3), "another seed dealt the same game");
+    }
+
+    #[test]
+    fn undo_and_redo_restore_the_exact_state() {
+        let mut engine = new_game(13);
+        let mut rng = ChaCha12Rng::seed_from_u64(13);
+        let mut undone = 0;
+
+        while engine.is_game_over().is_none() && engine.state.turn_number < 30 {
+            let player_id = engine.state.acting_player();
+            let legal = engine.legal_actions(player_id);
+            let action = legal[rng.gen_range(0..legal.len())].clone();
+
+            let before = (state_json(&engine), engine.rng.get_word_pos(), engine.replay().unwrap().actions.len());
+            engine.process_action(player_id, action).unwrap();
+            let after = (state_json(&engine), engine.rng.get_word_pos(), engine.replay().unwrap().actions.len());
+            if !engine.can_undo(player_id) {
+                continue;
+            }
+
+            engine.undo(player_id).unwrap();
+            assert_eq!((state_json(&engine), engine.rng.get_word_pos(), engine.replay().unwrap().actions.len()), before);
+            assert!(engine.can_redo(player_id));
+            engine.redo(player_id).unwrap();
+            assert_eq!((state_json(&engine), engine.rng.get_word_pos(), engine.replay().unwrap().actions.len()), after);
+            assert!(!engine.can_redo(player_id));
+            undone += 1;
+        }
+        assert!(undone > 10, "only {} actions could be undone", undone);
+    }
+
+    #[test]
+    fn undo_history_is_only_kept_when_it_can_be_used() {
+        let mut engine = new_game(4);
+        // Drawing can't be undone, so nothing is kept for it
+        engine.process_action(1, PlayerAction::EndPhase).unwrap();
+        assert!(engine.undo_stack.is_empty());
+        engine.process_action(1, PlayerAction::EndPhase).unwrap();
+        assert_eq!(engine.undo_stack.len(), 1);
+        assert!(engine.undo_stack[0].state.events.is_empty(), "the event log was copied");
+
+        engine.disable_undo();
+        assert!(!engine.can_undo(1));
+        play_random(&mut engine, 4, 100);
+        assert!(engine.undo_stack.is_empty() && engine.redo_stack.is_empty());
+    }
+
+    #[test]
+    fn undo_stops_at_hidden_information() {
+        let mut engine = new_game(2);
+        // Ending the draw phase draws a card, which can't be taken back
+        engine.process_action(1, PlayerAction::EndPhase).unwrap();
+        assert!(!engine.can_undo(1));
+        assert_eq!(engine.undo(1).unwrap_err(), GameError::NothingToUndo);
+
+        // Ending the placement phase can be, but only by the player who did it
+        engine.process_action(1, PlayerAction::EndPhase).unwrap();
+        assert!(engine.can_undo(1));
+        assert_eq!(engine.undo(2).unwrap_err(), GameError::NothingToUndo);
+
+        // A new action clears what could be redone
+        engine.undo(1).unwrap();
+        engine.process_action(1, PlayerAction::EndPhase).unwrap();
+        assert_eq!(engine.redo(1).unwrap_err(), GameError::NothingToRedo);
+    }
+
+    #[test]
+    fn attacks_wait_for_a_defender_with_a_face_down_feign() {
+        let mut engine = bare_game(GamePhase::Attack);
+        place_creature(&mut engine, 1, 1, 0); // Forest Wolf, 3/2
+        place_feign(&mut engine, 2, 16, 3); // Mana Boost
+
+        engine.process_action(1, PlayerAction::Attack { column: 0 }).unwrap();
+        assert!(engine.state.pending_attack.is_some());
+        assert_eq!(engine.state.acting_player(), 2);
+        assert_eq!(engine.state.player2.life, 20);
+
+        // Only the defender may act, and only by revealing a feign or passing
+        assert_eq!(engine.process_action(1, PlayerAction::EndPhase).unwrap_err(), GameError::AwaitingResponse);
+        assert_eq!(engine.process_action(2, PlayerAction::EndPhase).unwrap_err(), GameError::InvalidResponse);
+        assert!(engine.legal_actions(1).is_empty());
+        assert_eq!(engine.legal_actions(2), vec![PlayerAction::RevealFeign { column: 3 }, PlayerAction::PassResponse]);
+
+        engine.process_action(2, PlayerAction::PassResponse).unwrap();
+        assert!(engine.state.pending_attack.is_none());
+        assert_eq!(engine.state.player2.life, 17);
+        assert!(engine.state.player2.board.feigns[3].as_ref().is_some_and(|feign| !feign.is_revealed));
+    }
+
+    #[test]
+    fn feigns_revealed_in_response_act_on_the_attacked_lane() {
+        let mut engine = bare_game(GamePhase::Attack);
+        place_creature(&mut engine, 1, 1, 0); // Forest Wolf, 3/2
+        place_creature(&mut engine, 1, 2, 3); // Ancient Treant, 4/6
+        place_feign(&mut engine, 2, 15, 3); // Counter Strike, 3 damage to the opposing creature
+
+        engine.process_action(1, PlayerAction::Attack { column: 0 }).unwrap();
+        engine.process_action(2, PlayerAction::RevealFeign { column: 3 }).unwrap();
+
+        // The wolf in the attacked lane is struck down before it hits, not the treant behind the feign
+        assert!(creature(&engine, 1, 0).is_none());
+        assert_eq!(creature(&engine, 1, 3).unwrap().damage, 0);
+        assert_eq!(engine.state.player2.life, 20);
+        assert!(matches!(engine.state.events.last(), Some(GameEvent::AttackStopped { player_id: 1, column: 0 })));
+    }
+
+    #[test]
+    fn revealed_feigns_do_not_open_a_response_window() {
+        let mut engine = bare_game(GamePhase::Attack);
+        place_creature(&mut engine, 1, 1, 0); // Forest Wolf, 3/2
+        place_feign(&mut engine, 2, 16, 0); // Mana Boost
+        player_mut(&mut engine, 2).board.feigns[0].as_mut().unwrap().is_revealed = true;
+
+        engine.process_action(1, PlayerAction::Attack { column: 0 }).unwrap();
+
+        assert!(engine.state.pending_attack.is_none());
+        assert_eq!(engine.state.player2.life, 17);
+    }
+}
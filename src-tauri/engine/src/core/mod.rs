@@ -0,0 +1,15 @@
+pub mod game_engine;
+pub mod catalog;
+pub mod combat;
+pub mod effects;
+pub mod deck_builder;
+pub mod replay;
+pub mod save;
+
+pub use game_engine::GameEngine;
+pub use catalog::{CardCatalog, CatalogError};
+pub use combat::CombatResolver;
+pub use effects::EffectResolver;
+pub use save::{EngineSnapshot, SaveError, SaveMetadata, SaveSummary, SavedAi, SavedGame};
+pub use replay::{Replay, ReplayAction, ReplayError, ReplayFrame, ReplayPlayer};
+pub use deck_builder::{build_deck, create_mock_deck, mock_deck_list, validate_deck, DeckEntry, DeckImportError, DeckList, DeckRules, DeckViolation}; 
\ No newline at end of file
@@ -0,0 +1,204 @@
+use crate::models::*;
+use crate::core::game_engine::GameEngine;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Replay file format version understood by this build
+pub const REPLAY_VERSION: u32 = 2;
+
+/// Errors raised while loading or stepping through a replay
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayError {
+    Parse { message: String },
+    UnsupportedVersion { found: u32 },
+    Diverged { index: usize, message: String },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Parse { message } => write!(f, "malformed replay: {}", message),
+            ReplayError::UnsupportedVersion { found } => {
+                write!(f, "unsupported replay version {} (expected {})", found, REPLAY_VERSION)
+            }
+            ReplayError::Diverged { index, message } => {
+                write!(f, "replay action #{} was rejected: {}", index + 1, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+/// One recorded action and when it was taken
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayAction {
+    pub player_id: u32,
+    pub turn: u32,
+    pub phase: GamePhase,
+    pub action: PlayerAction,
+}
+
+/// Everything needed to play a game back exactly: the seed and decks that
+/// dealt it, and every accepted action in order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    pub seed: u64,
+    pub player1_name: String,
+    pub player2_name: String,
+    pub player1_deck: Vec<Card>, // Full definitions, so replays survive catalog changes
+    pub player2_deck: Vec<Card>,
+    pub actions: Vec<ReplayAction>,
+}
+
+impl Replay {
+    /// Start recording a game dealt from these decks
+    pub fn new(seed: u64, player1_name: String, player1_deck: Vec<Card>, player2_name: String, player2_deck: Vec<Card>) -> Self {
+        Replay {
+            version: REPLAY_VERSION,
+            seed,
+            player1_name,
+            player2_name,
+            player1_deck,
+            player2_deck,
+            actions: Vec::new(),
+        }
+    }
+
+    /// Serialize to the replay file format
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("replays always serialize")
+    }
+
+    /// Parse a replay file
+    pub fn from_json(text: &str) -> Result<Replay, ReplayError> {
+        let replay: Replay = serde_json::from_str(text)
+            .map_err(|e| ReplayError::Parse { message: e.to_string() })?;
+        if replay.version != REPLAY_VERSION {
+            return Err(ReplayError::UnsupportedVersion { found: replay.version });
+        }
+        Ok(replay)
+    }
+
+    /// A fresh engine in the replay's starting position
+    fn initial_engine(&self) -> GameEngine {
+        GameEngine::with_decks(
+            self.player1_name.clone(),
+            self.player1_deck.clone(),
+            self.player2_name.clone(),
+            self.player2_deck.clone(),
+            Some(self.seed),
+        )
+    }
+}
+
+/// Steps through a replay. States are computed once and kept, so stepping
+/// backward is as cheap as stepping forward.
+#[derive(Debug, Clone)]
+pub struct ReplayPlayer {
+    replay: Replay,
+    engine: GameEngine,
+    states: Vec<GameState>, // states[i] is the state after i actions
+    position: usize,
+}
+
+/// The replay position as shown to a viewer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayFrame {
+    pub position: usize, // Actions applied so far
+    pub length: usize,   // Total actions in the replay
+    pub last_action: Option<ReplayAction>,
+    pub state: GameState,
+}
+
+impl ReplayPlayer {
+    /// Load a replay at its starting position
+    pub fn new(replay: Replay) -> Self {
+        let engine = replay.initial_engine();
+        let states = vec![engine.state.clone()];
+        ReplayPlayer { replay, engine, states, position: 0 }
+    }
+
+    /// The replay being played
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    /// The current position
+    pub fn frame(&self) -> ReplayFrame {
+        ReplayFrame {
+            position: self.position,
+            length: self.replay.actions.len(),
+            last_action: self.position.checked_sub(1).map(|index| self.replay.actions[index].clone()),
+            state: self.states[self.position].clone(),
+        }
+    }
+
+    /// Apply the next action; stays put at the end of the replay
+    pub fn step_forward(&mut self) -> Result<ReplayFrame, ReplayError> {
+        if self.position < self.replay.actions.len() {
+            if self.position + 1 == self.states.len() {
+                let recorded = &self.replay.actions[self.position];
+                if let Err(error) = self.engine.process_action(recorded.player_id, recorded.action.clone()) {
+                    return Err(ReplayError::Diverged { index: self.position, message: error.to_string() });
+                }
+                self.states.push(self.engine.state.clone());
+            }
+            self.position += 1;
+        }
+        Ok(self.frame())
+    }
+
+    /// Undo the last applied action; stays put at the start of the replay
+    pub fn step_backward(&mut self) -> ReplayFrame {
+        self.position = self.position.saturating_sub(1);
+        self.frame()
+    }
+
+    /// Jump to the position after `position` actions (clamped to the end)
+    pub fn seek(&mut self, position: usize) -> Result<ReplayFrame, ReplayError> {
+        let target = position.min(self.replay.actions.len());
+        self.position = self.position.min(target);
+        while self.position < target {
+            self.step_forward()?;
+        }
+        Ok(self.frame())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::game_engine::tests::{new_game, play_random, state_json};
+
+    #[test]
+    fn replays_reach_the_final_state() {
+        let mut engine = new_game(5);
+        play_random(&mut engine, 5, 300);
+        let replay = Replay::from_json(&engine.replay().unwrap().to_json()).unwrap();
+        let length = replay.actions.len();
+
+        let mut player = ReplayPlayer::new(replay);
+        let end = player.seek(usize::MAX).unwrap();
+        assert_eq!(end.position, length);
+        assert_eq!(serde_json::to_value(&end.state).unwrap(), state_json(&engine));
+
+        // Stepping back and forward again lands on the same states
+        let before_end = player.step_backward();
+        assert_eq!(before_end.position, length - 1);
+        let end_again = player.step_forward().unwrap();
+        assert_eq!(serde_json::to_value(&end_again.state).unwrap(), state_json(&engine));
+    }
+
+    #[test]
+    fn diverging_replays_are_reported() {
+        let mut engine = new_game(5);
+        play_random(&mut engine, 5, 20);
+        let mut replay = engine.replay().unwrap().clone();
+        replay.actions[0].player_id = 2; // Player 1 opens the game
+
+        let error = ReplayPlayer::new(replay).step_forward().unwrap_err();
+        assert!(matches!(error, ReplayError::Diverged { index: 0, .. }), "{:?}", error);
+    }
+}
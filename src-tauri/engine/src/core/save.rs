@@ -0,0 +1,205 @@
+use crate::ai::AiKind;
+use crate::core::replay::Replay;
+use crate::models::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Save file format version written by this build. Bump it when the layout
+/// changes and teach `upgrade` to convert the previous version.
+pub const SAVE_VERSION: u32 = 2;
+
+/// Errors raised while reading a save file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SaveError {
+    Parse { message: String },
+    UnsupportedVersion { found: u32 },
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Parse { message } => write!(f, "malformed save file: {}", message),
+            SaveError::UnsupportedVersion { found } => {
+                write!(f, "unsupported save file version {} (this build reads up to {})", found, SAVE_VERSION)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+/// Everything the engine needs to resume a game exactly where it stopped
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineSnapshot {
+    pub state: GameState,
+    pub rng_position: u128, // Words consumed from the random stream seeded by `state.seed`
+    pub replay: Option<Replay>,
+}
+
+/// Summary of a saved game, readable without loading the whole game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveMetadata {
+    pub player1_name: String,
+    pub player2_name: String,
+    pub turn_number: u32,
+    pub saved_at: u64, // Seconds since the Unix epoch
+}
+
+/// The AI seated in a saved game
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SavedAi {
+    pub player_id: u32,
+    pub kind: AiKind,
+}
+
+/// A save file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedGame {
+    pub version: u32,
+    pub metadata: SaveMetadata,
+    pub engine: EngineSnapshot,
+    pub ai: Option<SavedAi>,
+}
+
+/// A save file as listed to the player; `id` names the file in the saves folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveSummary {
+    pub id: String,
+    pub metadata: SaveMetadata,
+}
+
+/// Just enough of a save file to list it
+#[derive(Deserialize)]
+struct SaveHeader {
+    version: u32,
+    metadata: SaveMetadata,
+}
+
+impl SavedGame {
+    /// Package a game for saving
+    pub fn new(engine: EngineSnapshot, ai: Option<SavedAi>, saved_at: u64) -> Self {
+        SavedGame {
+            version: SAVE_VERSION,
+            metadata: SaveMetadata {
+                player1_name: engine.state.player1.name.clone(),
+                player2_name: engine.state.player2.name.clone(),
+                turn_number: engine.state.turn_number,
+                saved_at,
+            },
+            engine,
+            ai,
+        }
+    }
+
+    /// Serialize to the save file format
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("saved games always serialize")
+    }
+
+    /// Parse a save file, upgrading older versions
+    pub fn from_json(text: &str) -> Result<SavedGame, SaveError> {
+        let parse_error = |e: serde_json::Error| SaveError::Parse { message: e.to_string() };
+
+        let value: serde_json::Value = serde_json::from_str(text).map_err(parse_error)?;
+        let version = value.get("version")
+            .and_then(|version| version.as_u64())
+            .ok_or_else(|| SaveError::Parse { message: "missing version".to_string() })? as u32;
+        serde_json::from_value(upgrade(value, version)?).map_err(parse_error)
+    }
+
+    /// Read only the metadata of a save file
+    pub fn metadata_from_json(text: &str) -> Result<SaveMetadata, SaveError> {
+        let header: SaveHeader = serde_json::from_str(text)
+            .map_err(|e| SaveError::Parse { message: e.to_string() })?;
+        if header.version > SAVE_VERSION {
+            return Err(SaveError::UnsupportedVersion { found: header.version });
+        }
+        Ok(header.metadata)
+    }
+}
+
+/// Convert a save file of any supported version to the current layout
+fn upgrade(mut value: serde_json::Value, version: u32) -> Result<serde_json::Value, SaveError> {
+    match version {
+        SAVE_VERSION => Ok(value),
+        // Version 1 kept a text log, which can't be turned back into events;
+        // the resumed game starts a fresh log
+        1 => {
+            if let Some(state) = value.pointer_mut("/engine/state").and_then(|state| state.as_object_mut()) {
+                state.remove("game_log");
+                state.insert("events".to_string(), serde_json::json!([]));
+            }
+            value["version"] = SAVE_VERSION.into();
+            Ok(value)
+        }
+        // Fields added to the engine since a save was written are filled by
+        // their serde defaults; structural changes are converted here
+        found => Err(SaveError::UnsupportedVersion { found }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::game_engine::tests::{new_game, play_random, state_json};
+    use crate::core::GameEngine;
+
+    /// Write `engine` to a save file and load it back
+    fn save_and_load(engine: &GameEngine) -> GameEngine {
+        let saved = SavedGame::new(engine.snapshot(), None, 0).to_json();
+        GameEngine::restore(SavedGame::from_json(&saved).unwrap().engine)
+    }
+
+    #[test]
+    fn restored_games_continue_where_they_stopped() {
+        let mut original = new_game(9);
+        play_random(&mut original, 9, 40);
+
+        let mut restored = save_and_load(&original);
+        assert_eq!(state_json(&restored), state_json(&original));
+        assert_ne!(original.snapshot().rng_position, 0);
+        assert_eq!(restored.snapshot().rng_position, original.snapshot().rng_position);
+
+        play_random(&mut original, 10, 200);
+        play_random(&mut restored, 10, 200);
+        assert_eq!(state_json(&restored), state_json(&original));
+        assert_eq!(restored.replay().unwrap().actions, original.replay().unwrap().actions);
+    }
+
+    #[test]
+    fn version_1_saves_upgrade_and_load() {
+        let mut engine = new_game(11);
+        play_random(&mut engine, 11, 40);
+
+        // Version 1 kept a text log in place of the event list
+        let mut v1 = serde_json::to_value(SavedGame::new(engine.snapshot(), None, 0)).unwrap();
+        v1["version"] = 1.into();
+        let state = v1.pointer_mut("/engine/state").unwrap().as_object_mut().unwrap();
+        state.remove("events");
+        state.insert("game_log".to_string(), serde_json::json!(["Game started!"]));
+
+        let saved = SavedGame::from_json(&v1.to_string()).unwrap();
+        assert_eq!(saved.version, SAVE_VERSION);
+        assert!(saved.engine.state.events.is_empty());
+
+        let mut restored = GameEngine::restore(saved.engine);
+        let mut expected = serde_json::to_value(&engine.state).unwrap();
+        expected["events"] = serde_json::json!([]);
+        assert_eq!(state_json(&restored), expected);
+        assert_eq!(restored.snapshot().rng_position, engine.snapshot().rng_position);
+
+        let player_id = restored.state.acting_player();
+        let action = restored.legal_actions(player_id)[0].clone();
+        restored.process_action(player_id, action).unwrap();
+    }
+
+    #[test]
+    fn newer_saves_are_refused() {
+        let mut saved = serde_json::to_value(SavedGame::new(new_game(1).snapshot(), None, 0)).unwrap();
+        saved["version"] = (SAVE_VERSION + 1).into();
+        assert_eq!(
+            SavedGame::from_json(&saved.to_string()).unwrap_err(),
+            SaveError::UnsupportedVersion { found: SAVE_VERSION + 1 }
+        );
+    }
+}
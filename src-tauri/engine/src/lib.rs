@@ -0,0 +1,7 @@
+// Game rules with no GUI dependencies, shared by the Tauri app, the
+// command-line client and any other tool that needs them
+pub mod models;
+pub mod core;
+pub mod ai;
+pub mod net;
+pub mod sim;
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::*;
+
+/// Why the engine rejected an action. Serialized with a `code` naming the
+/// variant, so callers can react to specific failures; `Display` gives the
+/// message shown to players. Failures of the app around the engine, such as
+/// unknown games or unreadable files, are the app's own errors.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "code", content = "details")]
+pub enum GameError {
+    // Rule violations
+    NotYourTurn,
+    WrongPhase { expected: GamePhase, actual: GamePhase },
+    CardNotInHand { instance_id: u32 },
+    NotEnoughMana { required: u32, available: u32 },
+    WrongCardType { expected: CardType, actual: CardType },
+    InvalidIndex { index: usize, max: usize },
+    ColumnOccupied { column: usize },
+    NoCreature { column: usize },
+    CreatureAlreadyAttacked { column: usize },
+    NoFeign { column: usize },
+    FeignAlreadyRevealed { column: usize },
+    NoPendingAttack,
+    AwaitingResponse,
+    InvalidResponse,
+    GameOver { winner: u32 },
+    NothingToUndo,
+    NothingToRedo,
+}
+
+impl GameError {
+    /// The machine-readable code of this error
+    pub fn code(&self) -> &'static str {
+        match self {
+            GameError::NotYourTurn => "NotYourTurn",
+            GameError::WrongPhase { .. } => "WrongPhase",
+            GameError::CardNotInHand { .. } => "CardNotInHand",
+            GameError::NotEnoughMana { .. } => "NotEnoughMana",
+            GameError::WrongCardType { .. } => "WrongCardType",
+            GameError::InvalidIndex { .. } => "InvalidIndex",
+            GameError::ColumnOccupied { .. } => "ColumnOccupied",
+            GameError::NoCreature { .. } => "NoCreature",
+            GameError::CreatureAlreadyAttacked { .. } => "CreatureAlreadyAttacked",
+            GameError::NoFeign { .. } => "NoFeign",
+            GameError::FeignAlreadyRevealed { .. } => "FeignAlreadyRevealed",
+            GameError::NoPendingAttack => "NoPendingAttack",
+            GameError::AwaitingResponse => "AwaitingResponse",
+            GameError::InvalidResponse => "InvalidResponse",
+            GameError::GameOver { .. } => "GameOver",
+            GameError::NothingToUndo => "NothingToUndo",
+            GameError::NothingToRedo => "NothingToRedo",
+        }
+    }
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameError::NotYourTurn => write!(f, "It's not your turn!"),
+            GameError::WrongPhase { expected, actual } => {
+                write!(f, "Can only do that during the {:?} phase (it is the {:?} phase)", expected, actual)
+            }
+            GameError::CardNotInHand { .. } => write!(f, "Card not found in hand"),
+            GameError::NotEnoughMana { required, available } => {
+                write!(f, "Not enough mana (needs {}, have {})", required, available)
+            }
+            GameError::WrongCardType { expected, actual } => {
+                write!(f, "Card is a {:?}, not a {:?}", actual, expected)
+            }
+            GameError::InvalidIndex { max, .. } => write!(f, "Column must be between 0 and {}", max),
+            GameError::ColumnOccupied { column } => write!(f, "Column {} is already occupied", column + 1),
+            GameError::NoCreature { column } => write!(f, "No creature in column {}", column + 1),
+            GameError::CreatureAlreadyAttacked { .. } => write!(f, "That creature has already attacked this turn"),
+            GameError::NoFeign { column } => write!(f, "No feign in column {}", column + 1),
+            GameError::FeignAlreadyRevealed { .. } => write!(f, "Feign already revealed"),
+            GameError::NoPendingAttack => write!(f, "There is no attack to respond to"),
+            GameError::AwaitingResponse => {
+                write!(f, "Waiting for the defending player to respond to the attack")
+            }
+            GameError::InvalidResponse => {
+                write!(f, "You can only reveal a feign or pass while responding to an attack")
+            }
+            GameError::GameOver { winner } => write!(f, "The game is over; player {} won", winner),
+            GameError::NothingToUndo => write!(f, "Nothing to undo"),
+            GameError::NothingToRedo => write!(f, "Nothing to redo"),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
+
+/// An error as sent to a player: its code and details plus the message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorView {
+    #[serde(flatten)]
+    pub error: GameError,
+    pub message: String,
+}
+
+impl From<GameError> for ErrorView {
+    fn from(error: GameError) -> Self {
+        ErrorView { message: error.to_string(), error }
+    }
+}
+
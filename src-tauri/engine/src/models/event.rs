@@ -0,0 +1,196 @@
+use serde::{Deserialize, Serialize};
+
+use super::*;
+
+/// Who an effect or attack landed on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EventTarget {
+    Player { player_id: u32, life: u32 }, // Life after the event
+    Creature { player_id: u32, instance_id: u32, name: String },
+}
+
+/// Something that happened in a game. The engine records every event in order;
+/// the text log is rendered from them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum GameEvent {
+    GameStarted,
+    TurnStarted { turn: u32, player_id: u32 },
+    PhaseChanged { phase: GamePhase },
+    // The drawn card is only known to the player who drew it
+    CardDrawn { player_id: u32, instance_id: Option<u32> },
+    DeckEmpty { player_id: u32 },
+    CreaturePlayed { player_id: u32, card: CardInstance, column: usize },
+    // Face-down feigns are only known to their owner
+    FeignPlayed { player_id: u32, card: Option<CardInstance>, column: usize },
+    EffectPlayed { player_id: u32, card: CardInstance },
+    EffectReplaced { name: String },
+    EffectExpired { name: String },
+    AttackDeclared { player_id: u32, column: usize },
+    ResponsePassed { player_id: u32 },
+    // The attacker was destroyed by a feign before it could strike
+    AttackStopped { player_id: u32, column: usize },
+    CreatureAttacked { player_id: u32, column: usize, name: String, attack: u32 },
+    CreatureBlocked { player_id: u32, column: usize, name: String, defense: u32 },
+    FeignRevealed { player_id: u32, card: CardInstance, column: usize },
+    // `source` names the creature or card dealing the damage
+    DamageDealt { source: String, target: EventTarget, amount: u32 },
+    Healed { target: EventTarget, amount: u32 },
+    ManaGained { player_id: u32, amount: u32 },
+    StatsModified { player_id: u32, instance_id: u32, name: String, attack: i32, defense: i32 },
+    ShieldRaised { player_id: u32, shield: u32 }, // Total shield after the event
+    TokenSummoned { player_id: u32, name: String, attack: u32, defense: u32, column: usize },
+    NoRoomForToken { player_id: u32, name: String },
+    CreatureSurvived { player_id: u32, instance_id: u32, name: String, defense: u32 },
+    CreatureDestroyed { player_id: u32, instance_id: u32, name: String },
+    CreatureFaded { player_id: u32, instance_id: u32, name: String },
+}
+
+impl GameEvent {
+    /// Render the event as a line of the game log, looking up player names
+    pub fn describe<'a>(&self, player_name: impl Fn(u32) -> &'a str) -> String {
+        match self {
+            GameEvent::GameStarted => "Game started!".to_string(),
+            GameEvent::TurnStarted { turn, player_id } => {
+                format!("Turn {}: {}'s turn begins", turn, player_name(*player_id))
+            }
+            GameEvent::PhaseChanged { phase } => match phase {
+                GamePhase::Draw => "Entering draw phase".to_string(),
+                GamePhase::Placement => "Entering placement phase".to_string(),
+                GamePhase::Attack => "Entering attack phase".to_string(),
+                GamePhase::EndTurn => "Entering end turn phase".to_string(),
+            },
+            GameEvent::CardDrawn { player_id, .. } => format!("{} draws a card", player_name(*player_id)),
+            GameEvent::DeckEmpty { player_id } => {
+                format!("{} cannot draw - deck is empty!", player_name(*player_id))
+            }
+            GameEvent::CreaturePlayed { player_id, card, column } => {
+                format!("{} plays {} (#{}) in column {}",
+                    player_name(*player_id), card.name, card.instance_id, column + 1)
+            }
+            GameEvent::FeignPlayed { player_id, column, .. } => {
+                format!("{} plays a feign card in column {}", player_name(*player_id), column + 1)
+            }
+            GameEvent::EffectPlayed { player_id, card } => {
+                format!("{} plays global effect: {} (#{})", player_name(*player_id), card.name, card.instance_id)
+            }
+            GameEvent::EffectReplaced { name } => format!("Global effect {} is replaced", name),
+            GameEvent::EffectExpired { name } => format!("Global effect {} expires", name),
+            GameEvent::AttackDeclared { player_id, column } => {
+                format!("{} declares an attack in column {}", player_name(*player_id), column + 1)
+            }
+            GameEvent::ResponsePassed { player_id } => format!("{} does not respond", player_name(*player_id)),
+            GameEvent::AttackStopped { .. } => {
+                "The attacking creature was destroyed before it could strike".to_string()
+            }
+            GameEvent::CreatureAttacked { player_id, name, attack, .. } => {
+                format!("{} attacks with {} (ATK: {})", player_name(*player_id), name, attack)
+            }
+            GameEvent::CreatureBlocked { player_id, name, defense, .. } => {
+                format!("{} defends with {} (DEF: {})", player_name(*player_id), name, defense)
+            }
+            GameEvent::FeignRevealed { player_id, card, .. } => {
+                format!("{} reveals feign: {} (#{})", player_name(*player_id), card.name, card.instance_id)
+            }
+            GameEvent::DamageDealt { source, target, amount } => match target {
+                EventTarget::Player { player_id, life } => {
+                    format!("{} deals {} damage to {} (Life: {})", source, amount, player_name(*player_id), life)
+                }
+                EventTarget::Creature { name, .. } => format!("{} deals {} damage to {}", source, amount, name),
+            },
+            GameEvent::Healed { target, amount } => match target {
+                EventTarget::Player { player_id, life } => {
+                    format!("{} heals {} (Life: {})", player_name(*player_id), amount, life)
+                }
+                EventTarget::Creature { name, .. } => format!("{} is healed for {}", name, amount),
+            },
+            GameEvent::ManaGained { player_id, amount } => {
+                format!("{} gains {} mana", player_name(*player_id), amount)
+            }
+            GameEvent::StatsModified { name, attack, defense, .. } => {
+                format!("{} gets {:+}/{:+}", name, attack, defense)
+            }
+            GameEvent::ShieldRaised { player_id, shield } => {
+                format!("{} is shielded from the next {} damage", player_name(*player_id), shield)
+            }
+            GameEvent::TokenSummoned { player_id, name, attack, defense, column } => {
+                format!("{} summons {} ({}/{}) in column {}",
+                    player_name(*player_id), name, attack, defense, column + 1)
+            }
+            GameEvent::NoRoomForToken { player_id, name } => {
+                format!("{} has no room to summon {}", player_name(*player_id), name)
+            }
+            GameEvent::CreatureSurvived { name, defense, .. } => {
+                format!("{} survives with {} defense remaining", name, defense)
+            }
+            GameEvent::CreatureDestroyed { name, .. } => format!("{} is destroyed!", name),
+            GameEvent::CreatureFaded { name, .. } => format!("{} fades away", name),
+        }
+    }
+
+    /// The event as `viewer_id` may see it, hiding the opponent's draws and
+    /// face-down feigns
+    pub fn redacted_for(&self, viewer_id: u32) -> GameEvent {
+        match self {
+            GameEvent::CardDrawn { player_id, .. } if *player_id != viewer_id => {
+                GameEvent::CardDrawn { player_id: *player_id, instance_id: None }
+            }
+            GameEvent::FeignPlayed { player_id, column, .. } if *player_id != viewer_id => {
+                GameEvent::FeignPlayed { player_id: *player_id, card: None, column: *column }
+            }
+            event => event.clone(),
+        }
+    }
+}
+
+impl GameState {
+    /// Name of a player by id
+    pub fn player_name(&self, player_id: u32) -> &str {
+        if player_id == 1 {
+            &self.player1.name
+        } else {
+            &self.player2.name
+        }
+    }
+
+    /// The game log as text, one line per event
+    pub fn game_log(&self) -> Vec<String> {
+        self.events.iter().map(|event| event.describe(|id| self.player_name(id))).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::game_engine::tests::{bare_game, give_card};
+
+    #[test]
+    fn opponents_see_draws_and_feigns_without_the_cards() {
+        let mut engine = bare_game(GamePhase::Draw);
+        engine.process_action(1, PlayerAction::EndPhase).unwrap();
+        let feign = give_card(&mut engine, 1, 16); // Mana Boost
+        engine.process_action(1, PlayerAction::PlayFeign { instance_id: feign, column: 0 }).unwrap();
+        engine.process_action(1, PlayerAction::RevealFeign { column: 0 }).unwrap();
+        let events = &engine.state.events;
+        let find = |events: &[GameEvent], matches: fn(&GameEvent) -> bool| events.iter().find(|e| matches(e)).cloned();
+
+        let own: Vec<_> = events.iter().map(|event| event.redacted_for(1)).collect();
+        let other: Vec<_> = events.iter().map(|event| event.redacted_for(2)).collect();
+        let drawn = |e: &GameEvent| matches!(e, GameEvent::CardDrawn { player_id: 1, .. });
+        let played = |e: &GameEvent| matches!(e, GameEvent::FeignPlayed { .. });
+        let revealed = |e: &GameEvent| matches!(e, GameEvent::FeignRevealed { .. });
+
+        assert!(matches!(find(&own, drawn), Some(GameEvent::CardDrawn { instance_id: Some(_), .. })));
+        assert!(matches!(find(&other, drawn), Some(GameEvent::CardDrawn { instance_id: None, .. })));
+        assert!(matches!(find(&own, played), Some(GameEvent::FeignPlayed { card: Some(card), .. }) if card.id == 16));
+        assert!(matches!(find(&other, played), Some(GameEvent::FeignPlayed { card: None, column: 0, .. })));
+        // Once revealed, the card is public
+        assert!(matches!(find(&other, revealed), Some(GameEvent::FeignRevealed { card, .. }) if card.id == 16));
+
+        // The text log never named the hidden cards in the first place
+        let log = |events: &[GameEvent]| {
+            events.iter().map(|e| e.describe(|id| engine.state.player_name(id))).collect::<Vec<_>>()
+        };
+        assert_eq!(log(&other), log(&own));
+        assert_eq!(log(&own), engine.state.game_log());
+    }
+}
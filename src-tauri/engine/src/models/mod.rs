@@ -0,0 +1,326 @@
+use serde::{Deserialize, Serialize};
+use std::ops::Deref;
+
+mod error;
+mod event;
+mod view;
+pub use error::*;
+pub use event::*;
+pub use view::*;
+
+/// Number of columns (lanes) on each player's board
+pub const BOARD_COLUMNS: usize = 4;
+
+/// Catalog id of the stand-in for a card only its holder knows, such as a
+/// network guest's cards in the host's game. No catalog card may use it.
+pub const HIDDEN_CARD_ID: u32 = u32::MAX;
+
+/// Represents the six color identities in Feign
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Color {
+    Verdant, // Green - Nature, growth, beast synergy
+    Cinder,  // Red - Aggression, damage, rage
+    Azure,   // Blue - Control, bounce, illusions
+    Ivory,   // White - Protection, healing, order
+    Umbral,  // Black - Sacrifice, decay, reanimation
+    Violet,  // Purple - Interaction with effect cards
+}
+
+/// Different types of cards in the game
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CardType {
+    Creature,
+    Feign,
+    Effect,
+}
+
+/// Who an effect applies to, relative to the card's owner and column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EffectTarget {
+    Owner,
+    Opponent,
+    FriendlyCreature, // The owner's creature in the card's column
+    OpposingCreature, // The opponent's creature in the card's column
+    AllFriendlyCreatures,
+    AllEnemyCreatures,
+    AllCreatures,
+}
+
+/// Effect primitives that card abilities are built from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CardEffect {
+    DealDamage { target: EffectTarget, amount: u32 },
+    Heal { target: EffectTarget, amount: u32 },
+    GrantMana { amount: u32 },
+    ModifyStats { target: EffectTarget, attack: i32, defense: i32 },
+    // Prevents the next `amount` damage dealt to the owner
+    Shield { amount: u32 },
+    // Creates a creature in the card's column (or the first open one)
+    SummonToken { name: String, attack: u32, defense: u32, duration: Option<u32> },
+    IfGlobalEffectActive { then: Vec<CardEffect>, otherwise: Vec<CardEffect> },
+}
+
+/// Continuous modifiers that apply while a global effect is active
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ContinuousEffect {
+    // Creatures (optionally only those of one color) get +attack/+defense
+    StatModifier { color: Option<Color>, attack: i32, defense: i32 },
+    // Every creature takes damage at the end of each turn
+    DamageCreaturesEachTurn { amount: u32 },
+    // The active player draws extra cards during their draw phase
+    ExtraDraw { count: u32 },
+    // All damage dealt is reduced by the amount
+    DamageReduction { amount: u32 },
+}
+
+/// Represents a card definition in the game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Card {
+    pub id: u32, // Catalog id shared by every copy of this card
+    pub name: String,
+    pub card_type: CardType,
+    pub color: Color,
+    pub mana_cost: u32,
+    pub description: String,
+    // Creature-specific stats (None for non-creatures)
+    pub attack: Option<u32>,
+    pub defense: Option<u32>,
+    // Effect-specific duration (None for non-effects)
+    pub duration: Option<u32>,
+    // Effects executed when a feign is revealed (feigns only)
+    #[serde(default)]
+    pub effects: Vec<CardEffect>,
+    // Modifiers applied while the card is the active global effect
+    #[serde(default)]
+    pub continuous_effects: Vec<ContinuousEffect>,
+}
+
+impl Card {
+    /// A stand-in for a card only its holder knows. It costs more mana than
+    /// anyone has, so the engine never plays it.
+    pub fn hidden() -> Card {
+        Card {
+            id: HIDDEN_CARD_ID,
+            name: "Hidden card".to_string(),
+            card_type: CardType::Feign,
+            color: Color::Verdant,
+            mana_cost: u32::MAX,
+            description: String::new(),
+            attack: None,
+            defense: None,
+            duration: None,
+            effects: Vec::new(),
+            continuous_effects: Vec::new(),
+        }
+    }
+
+    /// Whether this is a stand-in for a card only its holder knows
+    pub fn is_hidden(&self) -> bool {
+        self.id == HIDDEN_CARD_ID
+    }
+}
+
+/// A copy of a card in a game: its definition plus a game-unique instance id
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardInstance {
+    pub instance_id: u32,
+    #[serde(flatten)]
+    pub card: Card,
+}
+
+impl Deref for CardInstance {
+    type Target = Card;
+
+    fn deref(&self) -> &Card {
+        &self.card
+    }
+}
+
+/// Allocates game-unique card instance ids, starting from 1
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InstanceIdAllocator {
+    last_id: u32,
+}
+
+impl InstanceIdAllocator {
+    pub fn allocate(&mut self) -> u32 {
+        self.last_id += 1;
+        self.last_id
+    }
+}
+
+/// Represents a creature on the battlefield
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Creature {
+    pub card: CardInstance,
+    pub current_attack: u32,
+    pub current_defense: u32,
+    pub is_tapped: bool,
+    // Turns left before a temporary creature (e.g. a token) fades; None is permanent
+    #[serde(default)]
+    pub remaining_turns: Option<u32>,
+    // Permanent stat changes from card effects, on top of the base card stats
+    #[serde(default)]
+    pub attack_modifier: i32,
+    #[serde(default)]
+    pub defense_modifier: i32,
+    // Damage marked on the creature
+    #[serde(default)]
+    pub damage: u32,
+}
+
+impl Creature {
+    /// Put a creature card onto the battlefield with its base stats
+    pub fn new(card: CardInstance) -> Self {
+        Creature {
+            current_attack: card.attack.unwrap_or(0),
+            current_defense: card.defense.unwrap_or(0),
+            is_tapped: false,
+            remaining_turns: None,
+            attack_modifier: 0,
+            defense_modifier: 0,
+            damage: 0,
+            card,
+        }
+    }
+
+    /// Recompute current stats from base card stats, permanent modifiers,
+    /// marked damage and the bonus granted by continuous effects
+    pub fn refresh_stats(&mut self, attack_bonus: i32, defense_bonus: i32) {
+        let attack = self.card.attack.unwrap_or(0) as i32 + self.attack_modifier + attack_bonus;
+        let defense = self.card.defense.unwrap_or(0) as i32 + self.defense_modifier + defense_bonus
+            - self.damage as i32;
+        self.current_attack = attack.max(0) as u32;
+        self.current_defense = defense.max(0) as u32;
+    }
+}
+
+/// Represents a face-down feign card
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeignCard {
+    pub card: CardInstance,
+    pub is_revealed: bool,
+}
+
+/// Represents a global effect card
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlobalEffect {
+    pub card: CardInstance,
+    pub remaining_duration: u32,
+}
+
+/// Represents a player's board state
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PlayerBoard {
+    pub creatures: [Option<Creature>; BOARD_COLUMNS], // Front row, one creature per column
+    pub feigns: [Option<FeignCard>; BOARD_COLUMNS],   // Back row, one feign per column
+}
+
+impl PlayerBoard {
+    /// Create an empty board
+    pub fn new() -> Self {
+        PlayerBoard::default()
+    }
+}
+
+/// Represents a player in the game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Player {
+    pub id: u32,
+    pub name: String,
+    pub life: u32,
+    pub mana: u32,
+    #[serde(default)]
+    pub shield: u32, // Damage prevented before life is lost
+    pub hand: Vec<CardInstance>,
+    pub deck: Vec<CardInstance>,
+    pub board: PlayerBoard,
+}
+
+impl Player {
+    /// Deal damage to the player, absorbing it with any shield first.
+    /// Returns the amount of life actually lost.
+    pub fn take_damage(&mut self, amount: u32) -> u32 {
+        let absorbed = amount.min(self.shield);
+        self.shield -= absorbed;
+
+        let life_lost = (amount - absorbed).min(self.life);
+        self.life -= life_lost;
+        life_lost
+    }
+}
+
+/// Represents the current game state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameState {
+    pub player1: Player,
+    pub player2: Player,
+    pub current_player: u32, // 1 or 2
+    pub turn_number: u32,
+    pub phase: GamePhase,
+    pub global_effect: Option<GlobalEffect>,
+    // Attack waiting for the defending player to respond
+    #[serde(default)]
+    pub pending_attack: Option<PendingAttack>,
+    #[serde(default)]
+    pub instance_ids: InstanceIdAllocator,
+    // Seed of the game's random stream; never shown to players, since it
+    // would reveal the deck order
+    #[serde(default)]
+    pub seed: u64,
+    pub events: Vec<GameEvent>, // Everything that happened so far; see `game_log` for the text
+}
+
+impl GameState {
+    /// The player the game is waiting on: the defender while an attack is
+    /// pending, otherwise the player whose turn it is
+    pub fn acting_player(&self) -> u32 {
+        match &self.pending_attack {
+            Some(pending) => pending.defending_player(),
+            None => self.current_player,
+        }
+    }
+}
+
+/// A declared attack that has not resolved yet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingAttack {
+    pub attacking_player: u32,
+    pub column: usize,
+}
+
+impl PendingAttack {
+    /// The player who may respond to the attack
+    pub fn defending_player(&self) -> u32 {
+        if self.attacking_player == 1 { 2 } else { 1 }
+    }
+}
+
+/// Different phases of a turn
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GamePhase {
+    Draw,
+    Placement,
+    Attack,
+    EndTurn,
+}
+
+/// Actions a player can take
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PlayerAction {
+    PlayCreature { instance_id: u32, column: usize },
+    PlayFeign { instance_id: u32, column: usize },
+    PlayEffect { instance_id: u32 },
+    Attack { column: usize },
+    RevealFeign { column: usize },
+    // Decline to respond to a pending attack
+    PassResponse,
+    EndPhase,
+}
+
+/// Result of an accepted action
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionResult {
+    pub message: String,
+    pub new_state: GameState,
+} 
\ No newline at end of file
@@ -0,0 +1,194 @@
+use serde::{Deserialize, Serialize};
+
+use super::*;
+
+/// A feign slot as seen by one player; face-down cards hide their identity
+/// from everyone but their owner
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeignView {
+    pub card: Option<CardInstance>, // None when the viewer only sees the card back
+    pub is_revealed: bool,
+}
+
+/// A player's board as seen by one player
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoardView {
+    pub creatures: [Option<Creature>; BOARD_COLUMNS],
+    pub feigns: [Option<FeignView>; BOARD_COLUMNS],
+}
+
+/// A player as seen by one player: hidden zones are reduced to counts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerInfoView {
+    pub id: u32,
+    pub name: String,
+    pub life: u32,
+    pub mana: u32,
+    pub shield: u32,
+    pub hand: Vec<CardInstance>, // Empty unless the viewer owns this hand
+    pub hand_size: usize,
+    pub deck_size: usize,
+    pub board: BoardView,
+}
+
+/// The game state projected for a single player, with hidden information removed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerView {
+    pub viewer_id: u32,
+    pub player1: PlayerInfoView,
+    pub player2: PlayerInfoView,
+    pub current_player: u32,
+    pub turn_number: u32,
+    pub phase: GamePhase,
+    pub global_effect: Option<GlobalEffect>,
+    pub pending_attack: Option<PendingAttack>,
+    pub events: Vec<GameEvent>,
+    pub game_log: Vec<String>, // `events` rendered as text
+}
+
+/// Result of an action as seen by the acting player
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionResultView {
+    pub success: bool,
+    pub message: String,
+    pub new_state: Option<PlayerView>,
+    pub error: Option<ErrorView>, // Set when the action was rejected
+}
+
+/// Whether a player can currently undo or redo
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UndoAvailability {
+    pub can_undo: bool,
+    pub can_redo: bool,
+}
+
+impl PlayerInfoView {
+    fn new(player: &Player, viewer_id: u32) -> Self {
+        let is_owner = player.id == viewer_id;
+        let feigns = player.board.feigns.clone().map(|slot| {
+            slot.map(|feign| FeignView {
+                card: (is_owner || feign.is_revealed).then_some(feign.card),
+                is_revealed: feign.is_revealed,
+            })
+        });
+
+        PlayerInfoView {
+            id: player.id,
+            name: player.name.clone(),
+            life: player.life,
+            mana: player.mana,
+            shield: player.shield,
+            hand: if is_owner { player.hand.clone() } else { Vec::new() },
+            hand_size: player.hand.len(),
+            deck_size: player.deck.len(),
+            board: BoardView {
+                creatures: player.board.creatures.clone(),
+                feigns,
+            },
+        }
+    }
+}
+
+impl GameState {
+    /// Project the state for `viewer_id`, hiding the opponent's hand, both deck
+    /// orders and the identity of the opponent's face-down feigns
+    pub fn view_for(&self, viewer_id: u32) -> PlayerView {
+        let events: Vec<GameEvent> = self.events.iter().map(|event| event.redacted_for(viewer_id)).collect();
+        let game_log = events.iter().map(|event| event.describe(|id| self.player_name(id))).collect();
+
+        PlayerView {
+            viewer_id,
+            player1: PlayerInfoView::new(&self.player1, viewer_id),
+            player2: PlayerInfoView::new(&self.player2, viewer_id),
+            current_player: self.current_player,
+            turn_number: self.turn_number,
+            phase: self.phase.clone(),
+            global_effect: self.global_effect.clone(),
+            pending_attack: self.pending_attack.clone(),
+            events,
+            game_log,
+        }
+    }
+}
+
+impl ActionResultView {
+    /// Describe the outcome of an action for `viewer_id`, redacting the resulting state
+    pub fn new(result: &Result<ActionResult, GameError>, viewer_id: u32) -> Self {
+        match result {
+            Ok(result) => ActionResultView {
+                success: true,
+                message: result.message.clone(),
+                new_state: Some(result.new_state.view_for(viewer_id)),
+                error: None,
+            },
+            Err(error) => ActionResultView {
+                success: false,
+                message: error.to_string(),
+                new_state: None,
+                error: Some(ErrorView::from(error.clone())),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::game_engine::tests::{bare_game, new_game, place_feign, play_random};
+    use serde_json::Value;
+
+    /// Every card instance id mentioned anywhere in a view
+    fn instance_ids(view: &PlayerView) -> Vec<u64> {
+        fn walk(value: &Value, ids: &mut Vec<u64>) {
+            match value {
+                Value::Object(map) => {
+                    ids.extend(map.get("instance_id").and_then(Value::as_u64));
+                    map.values().for_each(|value| walk(value, ids));
+                }
+                Value::Array(values) => values.iter().for_each(|value| walk(value, ids)),
+                _ => {}
+            }
+        }
+        let mut ids = Vec::new();
+        walk(&serde_json::to_value(view).unwrap(), &mut ids);
+        ids
+    }
+
+    #[test]
+    fn views_hide_the_opponents_hand_and_both_decks() {
+        let mut engine = new_game(5);
+        play_random(&mut engine, 5, 40);
+        let state = &engine.state;
+
+        for (viewer, opponent) in [(&state.player1, &state.player2), (&state.player2, &state.player1)] {
+            let view = state.view_for(viewer.id);
+            let (own, theirs) = if viewer.id == 1 { (&view.player1, &view.player2) } else { (&view.player2, &view.player1) };
+            let ids = |cards: &[CardInstance]| cards.iter().map(|card| card.instance_id).collect::<Vec<_>>();
+            assert_eq!(ids(&own.hand), ids(&viewer.hand));
+            assert!(theirs.hand.is_empty());
+            assert_eq!((theirs.hand_size, theirs.deck_size), (opponent.hand.len(), opponent.deck.len()));
+
+            // Not even the events give away a hidden card
+            let seen = instance_ids(&view);
+            let hidden = opponent.hand.iter().chain(&opponent.deck).chain(&viewer.deck);
+            for card in hidden {
+                assert!(!seen.contains(&u64::from(card.instance_id)), "player {} sees #{}", viewer.id, card.instance_id);
+            }
+        }
+    }
+
+    #[test]
+    fn face_down_feigns_are_only_shown_to_their_owner() {
+        let mut engine = bare_game(GamePhase::Placement);
+        place_feign(&mut engine, 1, 16, 2); // Mana Boost
+
+        let hidden = engine.state.view_for(2).player1.board.feigns[2].clone().unwrap();
+        assert!(hidden.card.is_none() && !hidden.is_revealed);
+        assert_eq!(engine.state.view_for(1).player1.board.feigns[2].as_ref().unwrap().card.as_ref().unwrap().id, 16);
+
+        engine.process_action(1, PlayerAction::RevealFeign { column: 2 }).unwrap();
+        let revealed = engine.state.view_for(2).player1.board.feigns[2].clone().unwrap();
+        assert!(revealed.is_revealed);
+        assert_eq!(revealed.card.unwrap().id, 16);
+    }
+}
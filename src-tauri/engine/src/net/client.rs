@@ -0,0 +1,202 @@
+use super::commitment::GuestLedger;
+use crate::models::{ActionResultView, Card, GameError, PlayerAction, PlayerView, UndoAvailability};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use super::*;
+
+/// Something the host pushed without being asked
+#[derive(Debug, Clone)]
+pub enum ServerUpdate {
+    // Sent just before each State, so it is known by the time the view arrives
+    Undo(UndoAvailability),
+    State(Box<PlayerView>),
+    GameOver { winner: u32 },
+    // The connection closed, with the reason if it failed; no more updates follow
+    Disconnected { error: Option<NetError> },
+}
+
+/// A player's connection to a game hosted on another computer. The host runs
+/// the game; the client sends actions and receives its seat's view. The
+/// player's deck is shuffled and held here, so the host only learns a card
+/// when it is played or revealed. Every view is checked against both sides'
+/// commitments, and the connection is dropped if the host breaks one.
+pub struct GameClient {
+    seat: u32,
+    writer: MessageWriter,
+    results: Receiver<ActionResultView>,
+    ledger: Arc<Mutex<GuestLedger>>,
+}
+
+impl GameClient {
+    /// Join the game hosted at `addr` as `player_name`, drawing from `deck`,
+    /// returning the client and the first view. Pushed updates are passed to
+    /// `on_update` from a background thread. Gives up if the host does not
+    /// accept the connection within `CONNECT_TIMEOUT` or answer within
+    /// `HANDSHAKE_TIMEOUT`.
+    pub fn connect(
+        addr: impl ToSocketAddrs,
+        player_name: &str,
+        deck: Vec<Card>,
+        mut on_update: impl FnMut(ServerUpdate) + Send + 'static,
+    ) -> Result<(GameClient, PlayerView), NetError> {
+        let (mut ledger, deck) = GuestLedger::new(deck);
+        let (mut reader, writer) = split(connect(addr)?)?;
+        reader.set_timeout(Some(HANDSHAKE_TIMEOUT))?;
+        writer.send(&ClientMessage::Hello {
+            protocol_version: PROTOCOL_VERSION,
+            player_name: player_name.to_string(),
+            deck,
+        })?;
+
+        let (seat, mut view, sealed, dealt) = match reader.recv()? {
+            Some(ServerMessage::Welcome { protocol_version, seat, view, sealed, dealt })
+                if protocol_version == PROTOCOL_VERSION =>
+            {
+                (seat, view, sealed, dealt)
+            }
+            Some(ServerMessage::Welcome { protocol_version, .. }) => {
+                return Err(NetError::VersionMismatch { ours: PROTOCOL_VERSION, theirs: protocol_version });
+            }
+            Some(ServerMessage::Rejected { reason }) => return Err(NetError::Rejected { reason }),
+            Some(message) => {
+                return Err(NetError::Protocol { message: format!("expected Welcome, got {:?}", message) });
+            }
+            None => return Err(NetError::Disconnected),
+        };
+        // From here on the host may stay quiet while its player thinks
+        reader.set_timeout(None)?;
+
+        let checked = ledger.reveal_own(&mut view, &dealt).and_then(|_| ledger.check_host(&view, &sealed, &[]));
+        if let Err(e) = checked {
+            writer.shutdown();
+            return Err(e);
+        }
+        let ledger = Arc::new(Mutex::new(ledger));
+
+        // Replies are handed to the waiting request; everything else is an update
+        let (results_tx, results) = mpsc::channel();
+        let thread_ledger = ledger.clone();
+        let thread_writer = writer.clone();
+        std::thread::spawn(move || {
+            let error = loop {
+                match reader.recv::<ServerMessage>() {
+                    Ok(Some(ServerMessage::Result { mut result, dealt })) => {
+                        let mut ledger = thread_ledger.lock().unwrap_or_else(PoisonError::into_inner);
+                        let revealed = match result.new_state.as_mut() {
+                            Some(view) => ledger.reveal_own(view, &dealt),
+                            None if dealt.is_empty() => Ok(()),
+                            None => Err(NetError::Protocol { message: "cards dealt by a failed request".to_string() }),
+                        };
+                        drop(ledger);
+                        if let Err(e) = revealed {
+                            break Some(e);
+                        }
+                        if results_tx.send(result).is_err() {
+                            break None;
+                        }
+                    }
+                    Ok(Some(ServerMessage::State { mut view, sealed, opened, dealt, undo })) => {
+                        let mut ledger = thread_ledger.lock().unwrap_or_else(PoisonError::into_inner);
+                        let checked = ledger.reveal_own(&mut view, &dealt)
+                            .and_then(|_| ledger.check_host(&view, &sealed, &opened));
+                        drop(ledger);
+                        if let Err(e) = checked {
+                            break Some(e);
+                        }
+                        on_update(ServerUpdate::Undo(undo));
+                        on_update(ServerUpdate::State(Box::new(view)));
+                    }
+                    Ok(Some(ServerMessage::GameOver { winner })) => on_update(ServerUpdate::GameOver { winner }),
+                    Ok(Some(message)) => {
+                        break Some(NetError::Protocol { message: format!("unexpected message from host: {:?}", message) });
+                    }
+                    Ok(None) => break None,
+                    Err(e) => break Some(e),
+                }
+            };
+            thread_writer.shutdown();
+            on_update(ServerUpdate::Disconnected { error });
+        });
+
+        Ok((GameClient { seat, writer, results, ledger }, view))
+    }
+
+    /// The seat the host gave this player
+    pub fn seat(&self) -> u32 {
+        self.seat
+    }
+
+    /// Take an action; the host answers with its result. A card is played or
+    /// revealed with the opening of its commitment, and a feign is placed
+    /// face down with only its mana cost.
+    pub fn act(&mut self, action: PlayerAction) -> Result<ActionResultView, NetError> {
+        let (mut opening, mut cost) = (None, None);
+        {
+            let ledger = self.ledger.lock().unwrap_or_else(PoisonError::into_inner);
+            match &action {
+                PlayerAction::PlayCreature { instance_id, .. }
+                | PlayerAction::PlayEffect { instance_id, .. }
+                | PlayerAction::PlayFeign { instance_id, .. } => {
+                    let Some((card, card_opening)) = ledger.in_hand(*instance_id) else {
+                        let error = GameError::CardNotInHand { instance_id: *instance_id };
+                        return Ok(ActionResultView::new(&Err(error), self.seat));
+                    };
+                    match action {
+                        PlayerAction::PlayFeign { .. } => cost = Some(card.mana_cost),
+                        _ => opening = Some(card_opening.clone()),
+                    }
+                }
+                PlayerAction::RevealFeign { column } => opening = ledger.feign_opening(*column),
+                _ => {}
+            }
+        }
+        self.request(&ClientMessage::Action { action, opening, cost })
+    }
+
+    /// Take back this player's last action this turn
+    pub fn undo(&mut self) -> Result<ActionResultView, NetError> {
+        self.request(&ClientMessage::Undo)
+    }
+
+    /// Repeat this player's last undone action
+    pub fn redo(&mut self) -> Result<ActionResultView, NetError> {
+        self.request(&ClientMessage::Redo)
+    }
+
+    /// Send a request and wait for its `Result`. A host that does not answer
+    /// within `REPLY_TIMEOUT` is given up on, since a late reply would be
+    /// taken for the answer to the next request.
+    fn request(&mut self, message: &ClientMessage) -> Result<ActionResultView, NetError> {
+        self.writer.send(message)?;
+        let result = match self.results.recv_timeout(REPLY_TIMEOUT) {
+            Ok(result) => result,
+            Err(RecvTimeoutError::Timeout) => {
+                self.writer.shutdown();
+                return Err(NetError::TimedOut);
+            }
+            Err(RecvTimeoutError::Disconnected) => return Err(NetError::Disconnected),
+        };
+        Ok(result)
+    }
+}
+
+/// Connect to the first address of `addr` that accepts within `CONNECT_TIMEOUT`
+fn connect(addr: impl ToSocketAddrs) -> Result<TcpStream, NetError> {
+    let mut error = NetError::Io { message: "the address did not resolve".to_string() };
+    for addr in addr.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT) {
+            Ok(stream) => return Ok(stream),
+            Err(e) if e.kind() == std::io::ErrorKind::TimedOut => error = NetError::TimedOut,
+            Err(e) => error = e.into(),
+        }
+    }
+    Err(error)
+}
+
+impl Drop for GameClient {
+    fn drop(&mut self) {
+        self.writer.shutdown();
+    }
+}
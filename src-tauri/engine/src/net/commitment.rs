@@ -0,0 +1,392 @@
+use crate::models::{Card, CardInstance, CardType, GameEvent, GameState, PlayerView, BOARD_COLUMNS};
+use rand::rngs::OsRng;
+use rand::seq::SliceRandom;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::*;
+
+/// Prefix of every hashed opening, so commitments cannot be mistaken for
+/// hashes made for anything else
+const DOMAIN: &[u8] = b"feign/card-commitment/v1";
+
+/// Bytes of random salt per opening. Card ids are few enough to try them
+/// all, so the salt is what keeps a commitment from giving the card away.
+pub const SALT_BYTES: usize = 32;
+
+/// Binds a hidden card to its catalog id without revealing which card it is:
+/// the SHA-256 hash of the card id and a random salt. Sent as hex.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct CardCommitment([u8; 32]);
+
+/// What opens a commitment: the card it hides and the salt it was hidden with
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CardOpening {
+    pub card_id: u32,
+    #[serde(with = "hex_salt")]
+    pub salt: [u8; SALT_BYTES],
+}
+
+/// The commitment of a face-down feign, by column of its owner's back row
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SealedFeign {
+    pub column: usize,
+    pub commitment: CardCommitment,
+}
+
+/// The opening of a feign that was just revealed, by column of its owner's back row
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenedFeign {
+    pub column: usize,
+    pub opening: CardOpening,
+}
+
+/// A card the host dealt the joined player: which of the commitments they
+/// made to their deck it is
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DealtCard {
+    pub instance_id: u32,
+    pub index: usize,
+}
+
+impl CardOpening {
+    /// Hide `card_id` behind a fresh salt from the operating system
+    pub fn new(card_id: u32) -> Self {
+        let mut salt = [0; SALT_BYTES];
+        OsRng.fill_bytes(&mut salt);
+        CardOpening { card_id, salt }
+    }
+
+    /// The commitment this opening opens
+    pub fn commitment(&self) -> CardCommitment {
+        let mut hasher = Sha256::new();
+        hasher.update(DOMAIN);
+        hasher.update(self.card_id.to_be_bytes());
+        hasher.update(self.salt);
+        CardCommitment(hasher.finalize().into())
+    }
+}
+
+impl CardCommitment {
+    /// Whether `opening` opens this commitment
+    pub fn verify(&self, opening: &CardOpening) -> bool {
+        opening.commitment() == *self
+    }
+}
+
+impl From<CardCommitment> for String {
+    fn from(commitment: CardCommitment) -> String {
+        to_hex(&commitment.0)
+    }
+}
+
+impl TryFrom<String> for CardCommitment {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, String> {
+        from_hex(&text).map(CardCommitment)
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn from_hex<const N: usize>(text: &str) -> Result<[u8; N], String> {
+    if text.len() != N * 2 || !text.is_ascii() {
+        return Err(format!("expected {} hex digits", N * 2));
+    }
+    let mut bytes = [0; N];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&text[i * 2..i * 2 + 2], 16).map_err(|e| e.to_string())?;
+    }
+    Ok(bytes)
+}
+
+mod hex_salt {
+    use super::*;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(salt: &[u8; SALT_BYTES], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&to_hex(salt))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; SALT_BYTES], D::Error> {
+        from_hex(&String::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+/// The host's record of the hidden cards of both sides. It seals the host's
+/// own face-down feigns as they are placed and opens them once revealed. Of
+/// the remote player's cards it only ever holds commitments: it picks which
+/// commitment each of their cards is without seeing any, and checks the
+/// openings they play them with.
+#[derive(Debug)]
+pub(super) struct HostLedger {
+    remote_seat: u32,
+    own: [Option<(u32, CardOpening)>; BOARD_COLUMNS], // Opening of each of the host's feigns, with its card instance
+    events_seen: usize,
+    commitments: Vec<CardCommitment>, // The remote player's deck, in the order they committed to it
+    dealt_as: BTreeMap<u32, usize>,   // Commitment each of their card instances was dealt as
+    announced: BTreeSet<u32>,         // Card instances they were told they drew
+    deck: Vec<Card>,                  // The cards their deck must hold
+    opened: BTreeMap<u32, u32>,       // Catalog id of each of their card instances opened so far
+}
+
+impl HostLedger {
+    /// Start the ledger of a game whose remote player committed to a deck of
+    /// `deck`'s cards. Their cards in `state` are matched to the commitments at
+    /// random, so the order they committed in says nothing about the order
+    /// they draw in.
+    pub(super) fn new(state: &GameState, remote_seat: u32, commitments: Vec<CardCommitment>, deck: Vec<Card>) -> Self {
+        let remote = if remote_seat == 1 { &state.player1 } else { &state.player2 };
+        let mut indices: Vec<usize> = (0..commitments.len()).collect();
+        indices.shuffle(&mut OsRng);
+        let dealt_as = remote.hand.iter().chain(&remote.deck).map(|card| card.instance_id).zip(indices).collect();
+        HostLedger {
+            remote_seat,
+            own: Default::default(),
+            events_seen: 0,
+            commitments,
+            dealt_as,
+            announced: BTreeSet::new(),
+            deck,
+            opened: BTreeMap::new(),
+        }
+    }
+
+    /// Bring the ledger up to date with `state`, returning the commitments of
+    /// the host's face-down feigns and the openings of those revealed since
+    /// the last call
+    pub(super) fn update(&mut self, state: &GameState) -> (Vec<SealedFeign>, Vec<OpenedFeign>) {
+        let host_seat = if self.remote_seat == 1 { 2 } else { 1 };
+        let host = if host_seat == 1 { &state.player1 } else { &state.player2 };
+
+        // Undo can shorten the event list
+        let events_seen = self.events_seen.min(state.events.len());
+        let mut opened = Vec::new();
+        for event in &state.events[events_seen..] {
+            if let GameEvent::FeignRevealed { player_id, card, column } = event {
+                let sealed = self.own.get(*column).and_then(|slot| slot.as_ref());
+                if *player_id == host_seat && sealed.is_some_and(|(instance_id, _)| *instance_id == card.instance_id) {
+                    if let Some((_, opening)) = self.own[*column].take() {
+                        opened.push(OpenedFeign { column: *column, opening });
+                    }
+                }
+            }
+        }
+        self.events_seen = state.events.len();
+
+        let mut sealed = Vec::new();
+        for column in 0..BOARD_COLUMNS {
+            match &host.board.feigns[column] {
+                Some(feign) if !feign.is_revealed => {
+                    let current = self.own[column].as_ref().is_some_and(|(id, _)| *id == feign.card.instance_id);
+                    if !current {
+                        self.own[column] = Some((feign.card.instance_id, CardOpening::new(feign.card.id)));
+                    }
+                    if let Some((_, opening)) = &self.own[column] {
+                        sealed.push(SealedFeign { column, commitment: opening.commitment() });
+                    }
+                }
+                _ => self.own[column] = None,
+            }
+        }
+        (sealed, opened)
+    }
+
+    /// The cards in the remote player's hand they were not told about yet,
+    /// with the commitment each was dealt as
+    pub(super) fn deal(&mut self, state: &GameState) -> Vec<DealtCard> {
+        let remote = if self.remote_seat == 1 { &state.player1 } else { &state.player2 };
+        let mut dealt = Vec::new();
+        for card in &remote.hand {
+            if let Some(&index) = self.dealt_as.get(&card.instance_id) {
+                if self.announced.insert(card.instance_id) {
+                    dealt.push(DealtCard { instance_id: card.instance_id, index });
+                }
+            }
+        }
+        dealt
+    }
+
+    /// The remote player's card `instance_id`, if `opening` opens the
+    /// commitment it was dealt as and their deck holds that many copies of
+    /// the card
+    pub(super) fn open(&mut self, instance_id: u32, opening: &CardOpening) -> Option<Card> {
+        let index = *self.dealt_as.get(&instance_id)?;
+        if !self.commitments[index].verify(opening) {
+            return None;
+        }
+        let card = self.deck.iter().find(|card| card.id == opening.card_id)?.clone();
+        let copies = self.deck.iter().filter(|card| card.id == opening.card_id).count();
+        let others = self.opened.iter()
+            .filter(|&(&id, &opened)| id != instance_id && opened == opening.card_id)
+            .count();
+        if others >= copies {
+            return None;
+        }
+        self.opened.insert(instance_id, opening.card_id);
+        Some(card)
+    }
+
+    /// Whether a feign in the remote player's deck costs `mana`
+    pub(super) fn is_feign_cost(&self, mana: u32) -> bool {
+        self.deck.iter().any(|card| card.card_type == CardType::Feign && card.mana_cost == mana)
+    }
+}
+
+/// A joined player's record of the hidden cards of both sides. They shuffle
+/// their own deck and commit to every card, so the host deals them cards it
+/// cannot see, and every card the host shows for them must be the one it was
+/// dealt as. Every reveal of the host's feigns must open the commitment the
+/// feign was placed with, and a face-down feign's commitment may never change.
+#[derive(Debug)]
+pub(super) struct GuestLedger {
+    host: [Option<CardCommitment>; BOARD_COLUMNS],
+    deck: Vec<(Card, CardOpening)>,      // The joined player's deck, in the order they committed to it
+    dealt: BTreeMap<u32, usize>,         // Index into `deck` of each card instance dealt to them
+    hand: Vec<u32>,                      // Their hand in the last view
+    feigns: [Option<u32>; BOARD_COLUMNS], // Their face-down feigns in the last view
+}
+
+impl GuestLedger {
+    /// Shuffle the joined player's deck, returning the ledger and the
+    /// commitments to send the host, in the shuffled order. The host knows
+    /// which cards the deck holds, so an unshuffled order would tell it which
+    /// card each commitment hides.
+    pub(super) fn new(mut deck: Vec<Card>) -> (Self, Vec<CardCommitment>) {
+        deck.shuffle(&mut OsRng);
+        let deck: Vec<_> = deck.into_iter()
+            .map(|card| {
+                let opening = CardOpening::new(card.id);
+                (card, opening)
+            })
+            .collect();
+        let commitments = deck.iter().map(|(_, opening)| opening.commitment()).collect();
+        let ledger = GuestLedger {
+            host: Default::default(),
+            deck,
+            dealt: BTreeMap::new(),
+            hand: Vec::new(),
+            feigns: Default::default(),
+        };
+        (ledger, commitments)
+    }
+
+    /// Put the joined player's own cards in a view the host sent, in place of
+    /// the stand-ins the host holds, after learning which commitments the
+    /// cards just `dealt` were dealt as. A card the host shows as another than
+    /// the one it was dealt as breaks the deal.
+    pub(super) fn reveal_own(&mut self, view: &mut PlayerView, dealt: &[DealtCard]) -> Result<(), NetError> {
+        for DealtCard { instance_id, index } in dealt {
+            // Each commitment is dealt once, as a single card
+            let taken = self.dealt.iter().any(|(id, i)| i == index && id != instance_id);
+            let redealt = self.dealt.get(instance_id).is_some_and(|i| i != index);
+            if *index >= self.deck.len() || taken || redealt {
+                return Err(NetError::CardMismatch { instance_id: *instance_id });
+            }
+            self.dealt.insert(*instance_id, *index);
+        }
+
+        let own_id = view.viewer_id;
+        for card in own_cards(view, own_id) {
+            let mismatch = NetError::CardMismatch { instance_id: card.instance_id };
+            match self.dealt.get(&card.instance_id).map(|&index| &self.deck[index].0) {
+                Some(known) if card.is_hidden() => card.card = known.clone(),
+                Some(known) if card.id != known.id => return Err(mismatch),
+                None if card.is_hidden() => return Err(mismatch),
+                _ => {}
+            }
+        }
+
+        let own = if own_id == 1 { &view.player1 } else { &view.player2 };
+        self.hand = own.hand.iter().map(|card| card.instance_id).collect();
+        self.feigns = std::array::from_fn(|column| {
+            let feign = own.board.feigns[column].as_ref().filter(|feign| !feign.is_revealed)?;
+            feign.card.as_ref().map(|card| card.instance_id)
+        });
+        Ok(())
+    }
+
+    /// Check the host's side of a view against what it committed to before
+    pub(super) fn check_host(
+        &mut self,
+        view: &PlayerView,
+        sealed: &[SealedFeign],
+        opened: &[OpenedFeign],
+    ) -> Result<(), NetError> {
+        let host = if view.viewer_id == 1 { &view.player2 } else { &view.player1 };
+
+        for OpenedFeign { column, opening } in opened {
+            let column = *column;
+            let committed = self.host.get(column).copied().flatten();
+            if !committed.is_some_and(|commitment| commitment.verify(opening)) {
+                return Err(NetError::CommitmentMismatch { column });
+            }
+            // The card shown as revealed must be the one committed to
+            let revealed = view.events.iter().rev().find_map(|event| match event {
+                GameEvent::FeignRevealed { player_id, card, column: c } if *player_id == host.id && *c == column => {
+                    Some(card.id)
+                }
+                _ => None,
+            });
+            if revealed != Some(opening.card_id) {
+                return Err(NetError::CommitmentMismatch { column });
+            }
+            self.host[column] = None;
+        }
+
+        for column in 0..BOARD_COLUMNS {
+            let face_down = host.board.feigns[column].as_ref().is_some_and(|feign| !feign.is_revealed);
+            let commitment = sealed.iter().find(|s| s.column == column).map(|s| s.commitment);
+            match (face_down, commitment, self.host[column]) {
+                (false, _, _) => self.host[column] = None,
+                (true, None, _) => return Err(NetError::CommitmentMismatch { column }),
+                (true, Some(new), Some(old)) if new != old => return Err(NetError::CommitmentMismatch { column }),
+                (true, Some(new), _) => self.host[column] = Some(new),
+            }
+        }
+        Ok(())
+    }
+
+    /// A card in the joined player's hand, with the opening of its commitment
+    pub(super) fn in_hand(&self, instance_id: u32) -> Option<&(Card, CardOpening)> {
+        if !self.hand.contains(&instance_id) {
+            return None;
+        }
+        self.dealt.get(&instance_id).map(|&index| &self.deck[index])
+    }
+
+    /// The opening of the joined player's face-down feign in `column`
+    pub(super) fn feign_opening(&self, column: usize) -> Option<CardOpening> {
+        let instance_id = self.feigns.get(column).copied().flatten()?;
+        self.dealt.get(&instance_id).map(|&index| self.deck[index].1.clone())
+    }
+}
+
+/// Every card of `player_id`'s a view shows: in their hand, on their board and
+/// in the events
+fn own_cards(view: &mut PlayerView, player_id: u32) -> Vec<&mut CardInstance> {
+    let player = if player_id == 1 { &mut view.player1 } else { &mut view.player2 };
+    let mut cards: Vec<&mut CardInstance> = player.hand.iter_mut().collect();
+    cards.extend(player.board.creatures.iter_mut().flatten().map(|creature| &mut creature.card));
+    cards.extend(player.board.feigns.iter_mut().flatten().filter_map(|feign| feign.card.as_mut()));
+    for event in &mut view.events {
+        match event {
+            GameEvent::CreaturePlayed { player_id: owner, card, .. }
+            | GameEvent::EffectPlayed { player_id: owner, card }
+            | GameEvent::FeignRevealed { player_id: owner, card, .. }
+            | GameEvent::FeignPlayed { player_id: owner, card: Some(card), .. }
+                if *owner == player_id =>
+            {
+                cards.push(card)
+            }
+            _ => {}
+        }
+    }
+    cards
+}
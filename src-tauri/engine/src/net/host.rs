@@ -0,0 +1,368 @@
+use super::commitment::HostLedger;
+use crate::core::GameEngine;
+use crate::models::{ActionResult, ActionResultView, Card, GameError, PlayerAction};
+use std::io::ErrorKind;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+use super::*;
+
+/// The authoritative end of a network game: it owns the engine, accepts a
+/// player from another computer and applies their actions
+pub struct GameHost {
+    listener: TcpListener,
+    handshake_timeout: Duration,
+    cancelled: Arc<AtomicBool>,
+}
+
+/// Stops a host waiting in `GameHost::accept`, from any thread
+#[derive(Debug, Clone)]
+pub struct HostCanceller {
+    addr: SocketAddr,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl GameHost {
+    /// Start listening; port 0 picks a free one
+    pub fn bind(addr: impl ToSocketAddrs) -> Result<GameHost, NetError> {
+        Ok(GameHost {
+            listener: TcpListener::bind(addr)?,
+            handshake_timeout: HANDSHAKE_TIMEOUT,
+            cancelled: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    /// Address the host is listening on
+    pub fn local_addr(&self) -> Result<SocketAddr, NetError> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    /// How long a new connection may take to send `Hello` (`HANDSHAKE_TIMEOUT`
+    /// unless changed)
+    pub fn set_handshake_timeout(&mut self, timeout: Duration) {
+        self.handshake_timeout = timeout;
+    }
+
+    /// A handle that makes `accept` give up with `NetError::Cancelled`
+    pub fn canceller(&self) -> Result<HostCanceller, NetError> {
+        Ok(HostCanceller { addr: self.local_addr()?, cancelled: self.cancelled.clone() })
+    }
+
+    /// Wait for a player to connect and introduce themselves. Connections that
+    /// send something other than `Hello`, speak another protocol version (they
+    /// are told so) or stay silent past the handshake timeout are dropped, and
+    /// the host keeps waiting.
+    pub fn accept(&self) -> Result<PendingGuest, NetError> {
+        loop {
+            let stream = match self.listener.accept() {
+                Ok((stream, _)) => stream,
+                // The connection went away before it was accepted
+                Err(e) if matches!(e.kind(), ErrorKind::ConnectionAborted | ErrorKind::ConnectionReset) => continue,
+                Err(e) => return Err(e.into()),
+            };
+            if self.cancelled.load(Ordering::SeqCst) {
+                return Err(NetError::Cancelled);
+            }
+            if let Ok(guest) = self.handshake(stream) {
+                return Ok(guest);
+            }
+        }
+    }
+
+    /// Read the `Hello` of a new connection
+    fn handshake(&self, stream: TcpStream) -> Result<PendingGuest, NetError> {
+        let (mut reader, writer) = split(stream)?;
+        reader.set_timeout(Some(self.handshake_timeout))?;
+
+        match reader.recv()? {
+            Some(ClientMessage::Hello { protocol_version, player_name, deck })
+                if protocol_version == PROTOCOL_VERSION =>
+            {
+                // A seated player may think as long as they like
+                reader.set_timeout(None)?;
+                Ok(PendingGuest { player_name, deck, reader, writer })
+            }
+            Some(ClientMessage::Hello { protocol_version, .. }) => {
+                let error = NetError::VersionMismatch { ours: PROTOCOL_VERSION, theirs: protocol_version };
+                let _ = writer.send(&ServerMessage::Rejected { reason: error.to_string() });
+                writer.shutdown();
+                Err(error)
+            }
+            Some(message) => Err(NetError::Protocol { message: format!("expected Hello, got {:?}", message) }),
+            None => Err(NetError::Disconnected),
+        }
+    }
+}
+
+impl HostCanceller {
+    /// Stop the host waiting for a player. Has no effect once one was accepted.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        // Wake the blocked `accept` with a connection of our own
+        let ip = match self.addr.ip() {
+            IpAddr::V4(ip) if ip.is_unspecified() => IpAddr::V4(Ipv4Addr::LOCALHOST),
+            IpAddr::V6(ip) if ip.is_unspecified() => IpAddr::V6(Ipv6Addr::LOCALHOST),
+            ip => ip,
+        };
+        let _ = TcpStream::connect_timeout(&SocketAddr::new(ip, self.addr.port()), CONNECT_TIMEOUT);
+    }
+}
+
+/// A connected player waiting to be seated
+pub struct PendingGuest {
+    pub player_name: String,
+    deck: Vec<CardCommitment>, // One commitment per card of their shuffled deck
+    reader: MessageReader,
+    writer: MessageWriter,
+}
+
+impl PendingGuest {
+    /// Deal a game between the host's player in seat 1, drawing from
+    /// `host_deck`, and this player in seat 2, drawing from the deck they
+    /// committed to, which must hold the cards of `guest_deck`. The host's game
+    /// only holds stand-ins for the player's cards until they play them, so it
+    /// is not recorded for replay. Sends the player their first view.
+    pub fn deal(
+        self,
+        host_name: String,
+        host_deck: Vec<Card>,
+        guest_deck: Vec<Card>,
+        seed: Option<u64>,
+    ) -> Result<(GameEngine, RemotePlayer), NetError> {
+        if self.deck.len() != guest_deck.len() {
+            let reason = format!("expected a deck of {} cards, got {}", guest_deck.len(), self.deck.len());
+            self.reject(&reason)?;
+            return Err(NetError::Protocol { message: reason });
+        }
+
+        let seat = 2;
+        let hidden = vec![Card::hidden(); guest_deck.len()];
+        let mut engine = GameEngine::with_decks(host_name, host_deck, self.player_name, hidden, seed);
+        engine.disable_recording();
+        let mut ledger = HostLedger::new(&engine.state, seat, self.deck, guest_deck);
+        let (sealed, _) = ledger.update(&engine.state);
+        self.writer.send(&ServerMessage::Welcome {
+            protocol_version: PROTOCOL_VERSION,
+            seat,
+            view: engine.state.view_for(seat),
+            sealed,
+            dealt: ledger.deal(&engine.state),
+        })?;
+
+        // Messages are written on a thread of their own, in the order they were
+        // queued, so the game is never kept locked while the network is slow
+        let (outbox, queued) = mpsc::channel::<ServerMessage>();
+        let writer = self.writer.clone();
+        thread::spawn(move || {
+            for message in queued {
+                if writer.send(&message).is_err() {
+                    // Wakes the reader, which then sees the connection end
+                    writer.shutdown();
+                    break;
+                }
+            }
+        });
+
+        let link = RemoteLink { seat, outbox, writer: self.writer, ledger: Arc::new(Mutex::new(ledger)) };
+        Ok((engine, RemotePlayer { reader: self.reader, link }))
+    }
+
+    /// Turn the player away
+    pub fn reject(self, reason: &str) -> Result<(), NetError> {
+        self.writer.send(&ServerMessage::Rejected { reason: reason.to_string() })?;
+        self.writer.shutdown();
+        Ok(())
+    }
+}
+
+/// A seated player on another computer. Requests are read here; replies and
+/// updates go through its `RemoteLink`.
+pub struct RemotePlayer {
+    reader: MessageReader,
+    link: RemoteLink,
+}
+
+impl RemotePlayer {
+    /// The seat this player plays
+    pub fn seat(&self) -> u32 {
+        self.link.seat
+    }
+
+    /// A handle for sending to this player from any thread
+    pub fn link(&self) -> RemoteLink {
+        self.link.clone()
+    }
+
+    /// Wait for the player's next request; None once they disconnected
+    pub fn next_request(&mut self) -> Result<Option<ClientMessage>, NetError> {
+        self.reader.recv()
+    }
+}
+
+/// Sending side of a seated remote player, with the commitments of every
+/// hidden card. Sending only queues a message, so it never waits on the
+/// network.
+#[derive(Clone)]
+pub struct RemoteLink {
+    seat: u32,
+    outbox: Sender<ServerMessage>,
+    writer: MessageWriter,
+    ledger: Arc<Mutex<HostLedger>>,
+}
+
+impl RemoteLink {
+    /// The seat the remote player plays
+    pub fn seat(&self) -> u32 {
+        self.seat
+    }
+
+    /// Apply a request to the authoritative engine as the remote player's seat
+    /// and send them the result. Returns whether the game changed, in which
+    /// case every player should get an update. A player breaking their
+    /// commitments is an error, after which they should be disconnected.
+    pub fn apply(&self, engine: &mut GameEngine, request: ClientMessage) -> Result<bool, NetError> {
+        let result = match request {
+            ClientMessage::Action { action, opening, cost } => self.apply_action(engine, action, opening, cost)?,
+            ClientMessage::Undo => engine.undo(self.seat),
+            ClientMessage::Redo => engine.redo(self.seat),
+            ClientMessage::Hello { .. } => {
+                return Err(NetError::Protocol { message: "Hello sent twice".to_string() });
+            }
+        };
+        let dealt = self.ledger.lock().unwrap_or_else(PoisonError::into_inner).deal(&engine.state);
+        self.send(ServerMessage::Result { result: ActionResultView::new(&result, self.seat), dealt })?;
+        Ok(result.is_ok())
+    }
+
+    /// Apply an action, holding the remote player to the cards they committed
+    /// to. The engine only holds stand-ins for their cards, so a card is played
+    /// with its opening and swapped in first, and a feign is placed face down
+    /// as a stand-in costing what the player says it costs, until it is
+    /// revealed with its opening.
+    fn apply_action(
+        &self,
+        engine: &mut GameEngine,
+        action: PlayerAction,
+        opening: Option<CardOpening>,
+        cost: Option<u32>,
+    ) -> Result<Result<ActionResult, GameError>, NetError> {
+        let mut ledger = self.ledger.lock().unwrap_or_else(PoisonError::into_inner);
+        let player = if self.seat == 1 { &engine.state.player1 } else { &engine.state.player2 };
+        let in_hand = |instance_id| player.hand.iter().find(|card| card.instance_id == instance_id);
+
+        // Cards the player does not hold are left for the engine to refuse
+        let shown = match action {
+            PlayerAction::PlayCreature { instance_id, .. } | PlayerAction::PlayEffect { instance_id, .. }
+                if in_hand(instance_id).is_some() =>
+            {
+                let opening = opening.ok_or_else(|| NetError::Protocol {
+                    message: "a card must be played with its opening".to_string(),
+                })?;
+                let card = ledger.open(instance_id, &opening).ok_or(NetError::CardMismatch { instance_id })?;
+                Some((instance_id, card))
+            }
+            PlayerAction::PlayFeign { instance_id, .. } if in_hand(instance_id).is_some() => {
+                let cost = cost.ok_or_else(|| NetError::Protocol {
+                    message: "a feign must be placed with its mana cost".to_string(),
+                })?;
+                if !ledger.is_feign_cost(cost) {
+                    return Err(NetError::Protocol { message: format!("no feign in the deck costs {} mana", cost) });
+                }
+                // A card the host already knows, from an undone play, is placed as itself
+                let held = in_hand(instance_id).is_some_and(|card| card.is_hidden());
+                held.then(|| (instance_id, Card { mana_cost: cost, ..Card::hidden() }))
+            }
+            PlayerAction::RevealFeign { column } => {
+                let face_down = player.board.feigns.get(column).and_then(|slot| slot.as_ref())
+                    .filter(|feign| !feign.is_revealed);
+                // Missing or revealed feigns are left for the engine to refuse
+                match face_down {
+                    Some(feign) => {
+                        let placed = &feign.card;
+                        let card = opening.and_then(|opening| ledger.open(placed.instance_id, &opening))
+                            .filter(|card| card.card_type == placed.card_type && card.mana_cost == placed.mana_cost)
+                            .ok_or(NetError::CommitmentMismatch { column })?;
+                        Some((placed.instance_id, card))
+                    }
+                    None => None,
+                }
+            }
+            _ => None,
+        };
+
+        let Some((instance_id, card)) = shown else { return Ok(engine.process_action(self.seat, action)) };
+        let replaced = show_card(engine, self.seat, instance_id, card);
+        let result = engine.process_action(self.seat, action);
+        // A refused action leaves the stand-in where it was
+        if let (Err(_), Some(replaced)) = (&result, replaced) {
+            show_card(engine, self.seat, instance_id, replaced);
+        }
+        Ok(result)
+    }
+
+    /// Send the remote player their view of the game, and the winner once there
+    /// is one. The commitments of the host's face-down feigns go with it, the
+    /// openings of any revealed since the last update and the cards the player
+    /// drew.
+    pub fn send_update(&self, engine: &GameEngine) -> Result<(), NetError> {
+        let mut ledger = self.ledger.lock().unwrap_or_else(PoisonError::into_inner);
+        let (sealed, opened) = ledger.update(&engine.state);
+        let dealt = ledger.deal(&engine.state);
+        drop(ledger);
+        self.send(ServerMessage::State {
+            view: engine.state.view_for(self.seat),
+            sealed,
+            opened,
+            dealt,
+            undo: engine.undo_availability(self.seat),
+        })?;
+        if let Some(winner) = engine.is_game_over() {
+            self.send(ServerMessage::GameOver { winner })?;
+        }
+        Ok(())
+    }
+
+    /// Queue a message for the remote player
+    fn send(&self, message: ServerMessage) -> Result<(), NetError> {
+        self.outbox.send(message).map_err(|_| NetError::Disconnected)
+    }
+
+    /// Close the connection to the remote player
+    pub fn disconnect(&self) {
+        self.writer.shutdown();
+    }
+}
+
+/// Put `card` in place of `seat`'s card `instance_id`, in their hand or face
+/// down on their board, returning the card it replaced
+fn show_card(engine: &mut GameEngine, seat: u32, instance_id: u32, card: Card) -> Option<Card> {
+    let player = if seat == 1 { &mut engine.state.player1 } else { &mut engine.state.player2 };
+    let face_down = player.board.feigns.iter_mut().flatten().map(|feign| &mut feign.card);
+    player.hand.iter_mut().chain(face_down)
+        .find(|held| held.instance_id == instance_id)
+        .map(|held| std::mem::replace(&mut held.card, card))
+}
+
+/// Answer a remote player's requests until they disconnect, or until the
+/// connection fails and is closed. Every change they make is pushed back to
+/// them; the host's own changes must be sent with `RemoteLink::send_update`.
+pub fn serve(remote: &mut RemotePlayer, engine: &Mutex<GameEngine>) -> Result<(), NetError> {
+    let link = remote.link();
+    let served = (|| {
+        while let Some(request) = remote.next_request()? {
+            let mut engine = engine.lock().unwrap_or_else(PoisonError::into_inner);
+            if link.apply(&mut engine, request)? {
+                link.send_update(&engine)?;
+            }
+        }
+        Ok(())
+    })();
+    if served.is_err() {
+        link.disconnect();
+    }
+    served
+}
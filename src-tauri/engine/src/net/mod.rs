@@ -0,0 +1,136 @@
+pub mod client;
+pub mod commitment;
+pub mod host;
+pub mod protocol;
+
+pub use client::{GameClient, ServerUpdate};
+pub use commitment::{CardCommitment, CardOpening, DealtCard, OpenedFeign, SealedFeign};
+pub use host::{serve, GameHost, HostCanceller, PendingGuest, RemoteLink, RemotePlayer};
+pub use protocol::{ClientMessage, ServerMessage, PROTOCOL_VERSION};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, TcpStream};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+/// Port a host listens on unless another is chosen
+pub const DEFAULT_PORT: u16 = 7878;
+
+/// Longest message accepted from the other end, so a broken peer cannot
+/// exhaust memory
+pub const MAX_MESSAGE_BYTES: u64 = 4 * 1024 * 1024;
+
+/// How long a guest waits for the host to accept its connection
+pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long either side waits for the other to answer `Hello`: the host for a
+/// new connection to send it, the guest for the host's reply
+pub const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a guest waits for the `Result` of a request before giving up on
+/// the host
+pub const REPLY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Errors raised by a network game connection. Serialized like `GameError`,
+/// with a `code` naming the variant.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "code", content = "details")]
+pub enum NetError {
+    Io { message: String },
+    Protocol { message: String }, // The other end sent something unexpected
+    VersionMismatch { ours: u32, theirs: u32 },
+    Rejected { reason: String },
+    CommitmentMismatch { column: usize }, // The other end changed or misreported a face-down feign
+    CardMismatch { instance_id: u32 },    // A hidden card was dealt, played or shown as another than committed to
+    TimedOut,
+    Cancelled, // The host stopped waiting for a player
+    Disconnected,
+}
+
+impl fmt::Display for NetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetError::Io { message } => write!(f, "network error: {}", message),
+            NetError::Protocol { message } => write!(f, "protocol error: {}", message),
+            NetError::VersionMismatch { ours, theirs } => {
+                write!(f, "protocol version {} is not supported (expected {})", theirs, ours)
+            }
+            NetError::Rejected { reason } => write!(f, "the host refused the connection: {}", reason),
+            NetError::CommitmentMismatch { column } => {
+                write!(f, "the feign in column {} does not match its commitment", column + 1)
+            }
+            NetError::CardMismatch { instance_id } => {
+                write!(f, "card #{} does not match the commitment it was dealt as", instance_id)
+            }
+            NetError::TimedOut => write!(f, "the other computer did not answer in time"),
+            NetError::Cancelled => write!(f, "hosting was cancelled"),
+            NetError::Disconnected => write!(f, "the other player disconnected"),
+        }
+    }
+}
+
+impl std::error::Error for NetError {}
+
+impl From<std::io::Error> for NetError {
+    fn from(e: std::io::Error) -> Self {
+        NetError::Io { message: e.to_string() }
+    }
+}
+
+/// Receiving half of a connection: one JSON message per line
+struct MessageReader {
+    reader: BufReader<TcpStream>,
+}
+
+impl MessageReader {
+    /// Give up on a `recv` that waits longer than `timeout`; None waits forever
+    fn set_timeout(&self, timeout: Option<Duration>) -> Result<(), NetError> {
+        Ok(self.reader.get_ref().set_read_timeout(timeout)?)
+    }
+
+    /// Wait for the next message; None once the other end closed the connection
+    fn recv<T: DeserializeOwned>(&mut self) -> Result<Option<T>, NetError> {
+        let mut line = String::new();
+        let read = (&mut self.reader).take(MAX_MESSAGE_BYTES).read_line(&mut line)?;
+        if read == 0 {
+            return Ok(None);
+        }
+        if !line.ends_with('\n') {
+            return Err(NetError::Protocol { message: "message too long or cut off".to_string() });
+        }
+        serde_json::from_str(&line).map(Some).map_err(|e| NetError::Protocol { message: e.to_string() })
+    }
+}
+
+/// Sending half of a connection. Clones share the stream, so any thread can
+/// send without interleaving messages.
+#[derive(Clone)]
+struct MessageWriter {
+    stream: Arc<Mutex<TcpStream>>,
+}
+
+impl MessageWriter {
+    fn send<T: Serialize>(&self, message: &T) -> Result<(), NetError> {
+        let mut line = serde_json::to_string(message).map_err(|e| NetError::Protocol { message: e.to_string() })?;
+        line.push('\n');
+        let mut stream = self.stream.lock().unwrap_or_else(PoisonError::into_inner);
+        stream.write_all(line.as_bytes())?;
+        Ok(stream.flush()?)
+    }
+
+    /// Close both directions, waking a thread blocked reading the connection
+    fn shutdown(&self) {
+        let stream = self.stream.lock().unwrap_or_else(PoisonError::into_inner);
+        let _ = stream.shutdown(Shutdown::Both);
+    }
+}
+
+/// Split a stream into its reading and writing halves
+fn split(stream: TcpStream) -> Result<(MessageReader, MessageWriter), NetError> {
+    stream.set_nodelay(true)?;
+    let reader = MessageReader { reader: BufReader::new(stream.try_clone()?) };
+    Ok((reader, MessageWriter { stream: Arc::new(Mutex::new(stream)) }))
+}
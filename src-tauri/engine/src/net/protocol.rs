@@ -0,0 +1,71 @@
+use super::commitment::{CardCommitment, CardOpening, DealtCard, OpenedFeign, SealedFeign};
+use crate::models::*;
+use serde::{Deserialize, Serialize};
+
+/// Network protocol version understood by this build. See
+/// docs/network-protocol.md for the full specification.
+pub const PROTOCOL_VERSION: u32 = 3;
+
+/// Messages a joining player sends to the host
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ClientMessage {
+    // Must be the first message on a connection. `deck` commits to each card
+    // of the player's privately shuffled deck.
+    Hello {
+        protocol_version: u32,
+        player_name: String,
+        // Defaulted so a Hello of another version is still read, and refused for its version
+        #[serde(default)]
+        deck: Vec<CardCommitment>,
+    },
+    // PlayCreature, PlayEffect and RevealFeign carry the opening of the card,
+    // PlayFeign only the mana cost of the card placed face down
+    Action {
+        action: PlayerAction,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        opening: Option<CardOpening>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cost: Option<u32>,
+    },
+    Undo,
+    Redo,
+}
+
+/// Messages the host sends to a joined player
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ServerMessage {
+    // Reply to Hello: the seat the player was given, their first view, the
+    // commitments of the host's face-down feigns and which of their own
+    // commitments the cards of their opening hand were dealt as
+    Welcome {
+        protocol_version: u32,
+        seat: u32,
+        view: PlayerView,
+        sealed: Vec<SealedFeign>,
+        dealt: Vec<DealtCard>,
+    },
+    // Reply to Hello when the host will not seat the player; the host then closes the connection
+    Rejected { reason: String },
+    // Reply to every Action, Undo and Redo, in order, with the cards the
+    // request drew for the player
+    Result {
+        result: ActionResultView,
+        #[serde(default)]
+        dealt: Vec<DealtCard>,
+    },
+    // Sent whenever the game changes, whoever changed it, with the commitments
+    // of the host's face-down feigns, the openings of those just revealed, the
+    // cards the player drew and whether the player can undo or redo
+    State {
+        view: PlayerView,
+        sealed: Vec<SealedFeign>,
+        opened: Vec<OpenedFeign>,
+        #[serde(default)]
+        dealt: Vec<DealtCard>,
+        #[serde(default)]
+        undo: UndoAvailability,
+    },
+    GameOver { winner: u32 },
+}
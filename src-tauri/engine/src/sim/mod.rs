@@ -0,0 +1,84 @@
+pub mod report;
+
+pub use report::{CardStats, DamageStats, DeckStats, SeatStats, SimulationReport};
+
+use crate::ai::{self, AiKind, AiPlayer};
+use crate::core::GameEngine;
+use crate::models::*;
+use report::GameRecord;
+
+/// Turn limit after which a simulated game is called a draw
+pub const DEFAULT_MAX_TURNS: u32 = 100;
+
+/// A deck entered into a simulation and the AI that pilots it
+#[derive(Debug, Clone)]
+pub struct SimulatedDeck {
+    pub name: String,
+    pub cards: Vec<Card>,
+    pub ai: AiKind,
+}
+
+/// A batch of bot-vs-bot games between two decks. The decks swap seats every
+/// game, so seat and deck strength can be told apart.
+#[derive(Debug, Clone)]
+pub struct SimulationConfig {
+    pub decks: [SimulatedDeck; 2],
+    pub games: u32,
+    pub seed: u64, // Game N is dealt from seed + N
+    pub max_turns: u32,
+}
+
+/// Let two bots play a game to the end or to the turn limit, returning the
+/// winner, or None for a draw. Bots never take actions back, so no undo
+/// history is kept.
+pub fn play_bot_game(engine: &mut GameEngine, bots: &mut [Box<dyn AiPlayer + Send>; 2], max_turns: u32) -> Option<u32> {
+    engine.disable_undo();
+    loop {
+        if let Some(winner) = engine.is_game_over() {
+            return Some(winner);
+        }
+        if engine.state.turn_number > max_turns {
+            return None;
+        }
+
+        let player_id = engine.state.acting_player();
+        let bot = bots[player_id as usize - 1].as_mut();
+        ai::play_action(engine, bot, player_id)?;
+    }
+}
+
+/// Play every game of a simulation and gather the statistics
+pub fn run(config: &SimulationConfig) -> SimulationReport {
+    run_with_progress(config, |_| {})
+}
+
+/// Like `run`, calling `progress` with the number of games finished after each one
+pub fn run_with_progress(config: &SimulationConfig, mut progress: impl FnMut(u32)) -> SimulationReport {
+    let mut report = SimulationReport::new(config);
+
+    for game in 0..config.games {
+        // Deck 0 goes first in even games, deck 1 in odd ones
+        let first = (game % 2) as usize;
+        let seats = [first, 1 - first];
+        let [deck1, deck2] = seats.map(|deck| &config.decks[deck]);
+
+        let mut engine = GameEngine::with_decks(
+            deck1.name.clone(),
+            deck1.cards.clone(),
+            deck2.name.clone(),
+            deck2.cards.clone(),
+            Some(config.seed.wrapping_add(game as u64)),
+        );
+        let mut bots = [deck1.ai.create(), deck2.ai.create()];
+        let winner = play_bot_game(&mut engine, &mut bots, config.max_turns);
+
+        report.record(&GameRecord {
+            seats,
+            winner,
+            turns: engine.state.turn_number.min(config.max_turns),
+            events: &engine.state.events,
+        });
+        progress(game + 1);
+    }
+    report
+}
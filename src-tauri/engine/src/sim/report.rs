@@ -0,0 +1,366 @@
+use crate::ai::AiKind;
+use crate::models::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::SimulationConfig;
+
+/// Wins of whoever moved first or second
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SeatStats {
+    pub wins: u32,
+    pub win_rate: f64,
+}
+
+/// Wins of one of the simulated decks, whichever seat it had
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeckStats {
+    pub name: String,
+    pub ai: AiKind,
+    pub wins: u32,
+    pub win_rate: f64,
+}
+
+/// How often a card was played and how its player fared. Every game counts
+/// once per deck holding the card.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardStats {
+    pub card_id: u32,
+    pub name: String,
+    pub games_in_deck: u32,
+    pub games_played: u32, // Games in which at least one copy was played
+    pub copies_played: u32,
+    pub wins_when_played: u32,
+    pub play_rate: f64,
+    pub win_rate_when_played: f64,
+}
+
+/// Damage dealt by one source: a creature, feign, effect or global effect
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DamageStats {
+    pub source: String,
+    pub to_players: u32,
+    pub to_creatures: u32,
+    pub per_game: f64,
+}
+
+/// Statistics of a batch of simulated games
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationReport {
+    pub games: u32,
+    pub draws: u32, // Games stopped at the turn limit
+    pub average_turns: f64,
+    pub first_player: SeatStats,
+    pub second_player: SeatStats,
+    pub decks: [DeckStats; 2],
+    pub cards: Vec<CardStats>, // By name
+    pub damage: Vec<DamageStats>, // Most damage first
+    #[serde(skip)]
+    deck_cards: [BTreeSet<u32>; 2], // Card ids in each deck
+    #[serde(skip)]
+    total_turns: u64,
+}
+
+/// One finished game, as the report needs it
+pub(crate) struct GameRecord<'a> {
+    pub seats: [usize; 2], // Deck index playing as player 1 and player 2
+    pub winner: Option<u32>,
+    pub turns: u32,
+    pub events: &'a [GameEvent],
+}
+
+fn rate(count: u32, total: u32) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 / total as f64
+    }
+}
+
+impl SimulationReport {
+    pub(crate) fn new(config: &SimulationConfig) -> Self {
+        let mut cards = BTreeMap::new();
+        for card in config.decks.iter().flat_map(|deck| &deck.cards) {
+            cards.entry(card.id).or_insert_with(|| CardStats {
+                card_id: card.id,
+                name: card.name.clone(),
+                games_in_deck: 0,
+                games_played: 0,
+                copies_played: 0,
+                wins_when_played: 0,
+                play_rate: 0.0,
+                win_rate_when_played: 0.0,
+            });
+        }
+        let mut cards: Vec<_> = cards.into_values().collect();
+        cards.sort_by(|a, b| a.name.cmp(&b.name));
+
+        SimulationReport {
+            games: 0,
+            draws: 0,
+            average_turns: 0.0,
+            first_player: SeatStats::default(),
+            second_player: SeatStats::default(),
+            decks: config.decks.clone().map(|deck| DeckStats { name: deck.name, ai: deck.ai, wins: 0, win_rate: 0.0 }),
+            cards,
+            damage: Vec::new(),
+            deck_cards: config.decks.clone().map(|deck| deck.cards.iter().map(|card| card.id).collect()),
+            total_turns: 0,
+        }
+    }
+
+    /// Add a finished game to the statistics
+    pub(crate) fn record(&mut self, game: &GameRecord) {
+        self.games += 1;
+        self.total_turns += game.turns as u64;
+        let winning_deck = game.winner.map(|winner| game.seats[winner as usize - 1]);
+        match game.winner {
+            Some(1) => self.first_player.wins += 1,
+            Some(_) => self.second_player.wins += 1,
+            None => self.draws += 1,
+        }
+        if let Some(deck) = winning_deck {
+            self.decks[deck].wins += 1;
+        }
+
+        // Copies of each card played by each deck this game
+        let mut played: [BTreeMap<u32, u32>; 2] = Default::default();
+        for event in game.events {
+            match event {
+                GameEvent::CreaturePlayed { player_id, card, .. }
+                | GameEvent::FeignPlayed { player_id, card: Some(card), .. }
+                | GameEvent::EffectPlayed { player_id, card } => {
+                    let deck = game.seats[*player_id as usize - 1];
+                    *played[deck].entry(card.id).or_default() += 1;
+                }
+                GameEvent::DamageDealt { source, target, amount } => self.record_damage(source, target, *amount),
+                _ => {}
+            }
+        }
+
+        for card in &mut self.cards {
+            for (deck, (deck_cards, played)) in self.deck_cards.iter().zip(&played).enumerate() {
+                if !deck_cards.contains(&card.card_id) {
+                    continue;
+                }
+                card.games_in_deck += 1;
+                if let Some(copies) = played.get(&card.card_id) {
+                    card.games_played += 1;
+                    card.copies_played += copies;
+                    if winning_deck == Some(deck) {
+                        card.wins_when_played += 1;
+                    }
+                }
+            }
+        }
+
+        self.update_rates();
+    }
+
+    fn record_damage(&mut self, source: &str, target: &EventTarget, amount: u32) {
+        let index = match self.damage.iter().position(|stats| stats.source == source) {
+            Some(index) => index,
+            None => {
+                let stats = DamageStats { source: source.to_string(), to_players: 0, to_creatures: 0, per_game: 0.0 };
+                self.damage.push(stats);
+                self.damage.len() - 1
+            }
+        };
+        match target {
+            EventTarget::Player { .. } => self.damage[index].to_players += amount,
+            EventTarget::Creature { .. } => self.damage[index].to_creatures += amount,
+        }
+    }
+
+    fn update_rates(&mut self) {
+        self.average_turns = self.total_turns as f64 / self.games as f64;
+        self.first_player.win_rate = rate(self.first_player.wins, self.games);
+        self.second_player.win_rate = rate(self.second_player.wins, self.games);
+        for deck in &mut self.decks {
+            deck.win_rate = rate(deck.wins, self.games);
+        }
+        for card in &mut self.cards {
+            card.play_rate = rate(card.games_played, card.games_in_deck);
+            card.win_rate_when_played = rate(card.wins_when_played, card.games_played);
+        }
+        for stats in &mut self.damage {
+            stats.per_game = (stats.to_players + stats.to_creatures) as f64 / self.games as f64;
+        }
+        self.damage.sort_by(|a, b| b.per_game.total_cmp(&a.per_game).then_with(|| a.source.cmp(&b.source)));
+    }
+
+    /// Serialize the report as JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("reports always serialize")
+    }
+
+    /// Flatten the report into CSV rows of `section,name,metric,value`, so
+    /// every statistic fits one table
+    pub fn to_csv(&self) -> String {
+        let mut rows = vec!["section,name,metric,value".to_string()];
+        let mut row = |section: &str, name: &str, metric: &str, value: String| {
+            rows.push(format!("{},{},{},{}", section, csv_field(name), metric, value));
+        };
+
+        row("summary", "", "games", self.games.to_string());
+        row("summary", "", "draws", self.draws.to_string());
+        row("summary", "", "average_turns", format!("{:.2}", self.average_turns));
+        for (name, seat) in [("first", &self.first_player), ("second", &self.second_player)] {
+            row("seat", name, "wins", seat.wins.to_string());
+            row("seat", name, "win_rate", format!("{:.4}", seat.win_rate));
+        }
+        for deck in &self.decks {
+            row("deck", &deck.name, "wins", deck.wins.to_string());
+            row("deck", &deck.name, "win_rate", format!("{:.4}", deck.win_rate));
+        }
+        for card in &self.cards {
+            row("card", &card.name, "games_in_deck", card.games_in_deck.to_string());
+            row("card", &card.name, "games_played", card.games_played.to_string());
+            row("card", &card.name, "copies_played", card.copies_played.to_string());
+            row("card", &card.name, "play_rate", format!("{:.4}", card.play_rate));
+            row("card", &card.name, "win_rate_when_played", format!("{:.4}", card.win_rate_when_played));
+        }
+        for stats in &self.damage {
+            row("damage", &stats.source, "to_players", stats.to_players.to_string());
+            row("damage", &stats.source, "to_creatures", stats.to_creatures.to_string());
+            row("damage", &stats.source, "per_game", format!("{:.2}", stats.per_game));
+        }
+
+        rows.join("\n") + "\n"
+    }
+}
+
+/// Quote a CSV field if it needs it
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{build_deck, create_mock_deck, CardCatalog, DeckList, GameEngine};
+    use crate::sim::{self, SimulatedDeck};
+
+    fn config(games: u32) -> SimulationConfig {
+        let catalog = CardCatalog::bundled();
+        // Azure and Ivory, against the mock deck's Verdant and Cinder
+        let mut other = DeckList::default();
+        for card_id in [7, 8, 17, 22, 9, 10, 14, 23] {
+            other.add(card_id, 3);
+        }
+        SimulationConfig {
+            decks: [
+                SimulatedDeck { name: "Mock".to_string(), cards: create_mock_deck(catalog), ai: AiKind::Greedy },
+                SimulatedDeck { name: "Other".to_string(), cards: build_deck(&other, catalog), ai: AiKind::Greedy },
+            ],
+            games,
+            seed: 11,
+            max_turns: 60,
+        }
+    }
+
+    fn card<'a>(report: &'a SimulationReport, name: &str) -> &'a CardStats {
+        report.cards.iter().find(|card| card.name == name).unwrap()
+    }
+
+    fn instance(card_id: u32) -> CardInstance {
+        CardInstance { instance_id: 0, card: CardCatalog::bundled().get(card_id).unwrap().clone() }
+    }
+
+    #[test]
+    fn games_are_counted_by_seat_deck_and_card() {
+        let config = config(0);
+        let mut report = SimulationReport::new(&config);
+        let wolf = instance(1);
+        let hit = |amount, target| GameEvent::DamageDealt { source: "Forest Wolf".to_string(), target, amount };
+        let face = EventTarget::Player { player_id: 2, life: 15 };
+        let creature = EventTarget::Creature { player_id: 2, instance_id: 9, name: "Holy Knight".to_string() };
+
+        // The mock deck goes first, plays two wolves and wins
+        let events = [
+            GameEvent::CreaturePlayed { player_id: 1, card: wolf.clone(), column: 0 },
+            GameEvent::CreaturePlayed { player_id: 1, card: wolf.clone(), column: 1 },
+            hit(3, face.clone()),
+            hit(2, creature),
+        ];
+        report.record(&GameRecord { seats: [0, 1], winner: Some(1), turns: 10, events: &events });
+        // Then goes second, plays none and loses
+        report.record(&GameRecord { seats: [1, 0], winner: Some(1), turns: 20, events: &[] });
+        // And draws after playing one
+        let events = [GameEvent::CreaturePlayed { player_id: 2, card: wolf, column: 0 }, hit(1, face)];
+        report.record(&GameRecord { seats: [1, 0], winner: None, turns: 30, events: &events });
+
+        assert_eq!((report.games, report.draws, report.average_turns), (3, 1, 20.0));
+        assert_eq!((report.first_player.wins, report.second_player.wins), (2, 0));
+        assert_eq!(report.decks.each_ref().map(|deck| deck.wins), [1, 1]);
+        assert!((report.first_player.win_rate - 2.0 / 3.0).abs() < 1e-9);
+        assert!((report.decks[0].win_rate - 1.0 / 3.0).abs() < 1e-9);
+
+        let wolf = card(&report, "Forest Wolf");
+        assert_eq!((wolf.games_in_deck, wolf.games_played, wolf.copies_played, wolf.wins_when_played), (3, 2, 3, 1));
+        assert!((wolf.play_rate - 2.0 / 3.0).abs() < 1e-9);
+        assert!((wolf.win_rate_when_played - 0.5).abs() < 1e-9);
+        let knight = card(&report, "Holy Knight");
+        assert_eq!((knight.games_in_deck, knight.games_played, knight.play_rate), (3, 0, 0.0));
+
+        assert_eq!(report.damage.len(), 1);
+        let damage = &report.damage[0];
+        assert_eq!((damage.to_players, damage.to_creatures), (4, 2));
+        assert!((damage.per_game - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn simulated_games_add_up() {
+        let config = config(6);
+        let report = sim::run(&config);
+
+        // Play the same games again and total what the report should hold
+        let mut wins = [0; 2];
+        let mut copies: BTreeMap<u32, u32> = BTreeMap::new();
+        let mut damage: BTreeMap<String, u32> = BTreeMap::new();
+        for game in 0..config.games {
+            let seats = if game % 2 == 0 { [0, 1] } else { [1, 0] };
+            let [deck1, deck2] = seats.map(|deck| &config.decks[deck]);
+            let mut engine = GameEngine::with_decks(
+                deck1.name.clone(),
+                deck1.cards.clone(),
+                deck2.name.clone(),
+                deck2.cards.clone(),
+                Some(config.seed + game as u64),
+            );
+            let mut bots = [AiKind::Greedy.create(), AiKind::Greedy.create()];
+            if let Some(winner) = sim::play_bot_game(&mut engine, &mut bots, config.max_turns) {
+                wins[seats[winner as usize - 1]] += 1;
+            }
+            for event in &engine.state.events {
+                match event {
+                    GameEvent::CreaturePlayed { card, .. }
+                    | GameEvent::FeignPlayed { card: Some(card), .. }
+                    | GameEvent::EffectPlayed { card, .. } => *copies.entry(card.id).or_default() += 1,
+                    GameEvent::DamageDealt { source, amount, .. } => *damage.entry(source.clone()).or_default() += amount,
+                    _ => {}
+                }
+            }
+        }
+
+        assert_eq!(report.games, 6);
+        assert_eq!(report.decks.each_ref().map(|deck| deck.wins), wins);
+        assert_eq!(report.first_player.wins + report.second_player.wins + report.draws, 6);
+        assert_eq!(report.decks[0].wins + report.decks[1].wins, report.first_player.wins + report.second_player.wins);
+        for card in &report.cards {
+            assert_eq!(card.games_in_deck, 6, "{} is in one deck", card.name);
+            assert_eq!(card.copies_played, copies.get(&card.card_id).copied().unwrap_or(0), "{}", card.name);
+            assert!(card.games_played <= card.copies_played && card.wins_when_played <= card.games_played);
+            assert!((card.play_rate - card.games_played as f64 / 6.0).abs() < 1e-9);
+        }
+        assert!(report.cards.iter().any(|card| card.copies_played > 0));
+        let reported: BTreeMap<String, u32> =
+            report.damage.iter().map(|stats| (stats.source.clone(), stats.to_players + stats.to_creatures)).collect();
+        assert_eq!(reported, damage);
+        assert!(report.damage.windows(2).all(|pair| pair[0].per_game >= pair[1].per_game), "not sorted by damage");
+    }
+}
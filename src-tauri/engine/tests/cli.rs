@@ -0,0 +1,109 @@
+// Runs the feign-cli binary the way a user would
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+fn cli(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_feign-cli")).args(args).output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+/// A file path in the temp directory, removed again when dropped
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(name: &str) -> Self {
+        TempFile(std::env::temp_dir().join(format!("feign-cli-{}-{}", std::process::id(), name)))
+    }
+
+    fn path(&self) -> &str {
+        self.0.to_str().unwrap()
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+#[test]
+fn a_recorded_match_replays_to_the_same_log() {
+    let replay = TempFile::new("match.json");
+    let played = stdout(&cli(&["match", "--seed", "5", "--replay", replay.path()]));
+    let replayed = stdout(&cli(&["log", replay.path()]));
+
+    // Both print the game log, then their own closing lines
+    let log = |output: &str| {
+        output.lines()
+            .take_while(|line| !line.contains(" wins on turn ") && !line.ends_with(" won") && !line.starts_with("Draw"))
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+    assert!(log(&played).len() > 10);
+    assert_eq!(log(&played), log(&replayed));
+}
+
+#[test]
+fn matches_of_several_games_print_a_tally() {
+    let output = stdout(&cli(&["match", "--seed", "1", "--games", "3", "--p1-ai", "greedy", "--p2-ai", "greedy"]));
+
+    assert!(output.starts_with("3 games\n"), "{}", output);
+    let counts: u32 = output.lines().skip(1)
+        .map(|line| line.rsplit(' ').find_map(|word| word.parse::<u32>().ok()).unwrap())
+        .sum();
+    assert_eq!(counts, 3);
+}
+
+#[test]
+fn simulations_report_as_csv() {
+    let output = stdout(&cli(&["simulate", "--games", "4", "--seed", "2", "--format", "csv", "--quiet"]));
+
+    let mut lines = output.lines();
+    assert_eq!(lines.next(), Some("section,name,metric,value"));
+    assert!(lines.any(|line| line == "summary,,games,4"));
+}
+
+#[test]
+fn illegal_decks_are_refused() {
+    let deck = TempFile::new("deck.txt");
+    std::fs::write(&deck.0, "1 Forest Wolf\n").unwrap();
+
+    let output = cli(&["simulate", "--deck1", deck.path(), "--games", "1", "--quiet"]);
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("is not a legal deck"));
+}
+
+#[test]
+fn play_takes_typed_commands() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_feign-cli"))
+        .args(["play", "--seed", "1"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"end\nattack 9\nactions\nend\nquit\n").unwrap();
+    let output = stdout(&child.wait_with_output().unwrap());
+
+    assert!(output.contains("Phase advanced to Placement"));
+    assert!(output.contains("Columns go from 1 to 4"));
+    assert!(output.contains("  1. "), "no actions listed:\n{}", output);
+    assert!(output.contains("Phase advanced to Attack"));
+}
+
+#[test]
+fn bad_arguments_are_explained() {
+    let unknown = cli(&["dance"]);
+    assert!(!unknown.status.success());
+    assert!(String::from_utf8_lossy(&unknown.stderr).starts_with("Usage:"));
+
+    let not_a_number = cli(&["match", "--games", "many"]);
+    assert!(!not_a_number.status.success());
+    assert!(String::from_utf8_lossy(&not_a_number.stderr).contains("--games expects a number, got many"));
+}
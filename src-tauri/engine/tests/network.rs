@@ -0,0 +1,662 @@
+// Runs both ends of a network game on localhost
+
+use feign_engine::ai::{self, GreedyAi};
+use feign_engine::core::{create_mock_deck, CardCatalog, GameEngine};
+use feign_engine::models::*;
+use feign_engine::net::{
+    self, CardOpening, DealtCard, GameClient, GameHost, NetError, RemotePlayer, ServerMessage, ServerUpdate,
+    PROTOCOL_VERSION,
+};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Host a game on a free port. The returned thread seats the first player to
+/// join as player 2 and hands back the engine and their connection.
+fn host_game(seed: u64) -> (SocketAddr, JoinHandle<(GameEngine, RemotePlayer)>) {
+    host_game_with(seed, mock_deck(), |_| {})
+}
+
+/// Host a game whose guest draws from `guest_deck`, arranging it with `setup`
+/// once they are seated. The guest is sent the arranged game.
+fn host_game_with(
+    seed: u64,
+    guest_deck: Vec<Card>,
+    setup: impl FnOnce(&mut GameEngine) + Send + 'static,
+) -> (SocketAddr, JoinHandle<(GameEngine, RemotePlayer)>) {
+    let host = GameHost::bind("127.0.0.1:0").unwrap();
+    let addr = host.local_addr().unwrap();
+    let handle = thread::spawn(move || {
+        let guest = host.accept().unwrap();
+        let (mut engine, remote) = guest.deal("Host".to_string(), mock_deck(), guest_deck, Some(seed)).unwrap();
+        setup(&mut engine);
+        remote.link().send_update(&engine).unwrap();
+        (engine, remote)
+    });
+    (addr, handle)
+}
+
+/// The deck players draw from unless a test picks another
+fn mock_deck() -> Vec<Card> {
+    create_mock_deck(CardCatalog::bundled())
+}
+
+/// A deck of nothing but feigns, so the opening hand holds some
+fn feign_deck() -> Vec<Card> {
+    let feigns = CardCatalog::bundled().by_type(&CardType::Feign);
+    feigns.iter().flat_map(|&card| std::iter::repeat_n(card.clone(), 4)).collect()
+}
+
+/// Start `seat`'s placement phase with mana to spare
+fn ready_to_place(engine: &mut GameEngine, seat: u32) {
+    engine.state.current_player = seat;
+    engine.state.phase = GamePhase::Placement;
+    let player = if seat == 1 { &mut engine.state.player1 } else { &mut engine.state.player2 };
+    player.mana = 10;
+}
+
+/// Start the host's placement phase with a feign from their deck in hand,
+/// returning its instance id
+fn ready_to_place_feign(engine: &mut GameEngine) -> u32 {
+    ready_to_place(engine, 1);
+    let player = &mut engine.state.player1;
+    let index = player.deck.iter().position(|card| card.card_type == CardType::Feign).expect("no feign in the deck");
+    let card = player.deck.remove(index);
+    let instance_id = card.instance_id;
+    player.hand.push(card);
+    instance_id
+}
+
+/// Wait for the connection to drop, returning why
+fn wait_for_disconnect(updates: &Receiver<ServerUpdate>) -> Option<NetError> {
+    loop {
+        if let ServerUpdate::Disconnected { error } = updates.recv_timeout(TIMEOUT).expect("no update from the host") {
+            return error;
+        }
+    }
+}
+
+/// Join a game, collecting pushed updates on a channel
+fn join(addr: SocketAddr) -> (GameClient, PlayerView, Receiver<ServerUpdate>) {
+    join_with(addr, mock_deck())
+}
+
+/// Join a game drawing from `deck`
+fn join_with(addr: SocketAddr, deck: Vec<Card>) -> (GameClient, PlayerView, Receiver<ServerUpdate>) {
+    let (updates_tx, updates) = mpsc::channel();
+    let (client, view) = GameClient::connect(addr, "Guest", deck, move |update| {
+        let _ = updates_tx.send(update);
+    })
+    .unwrap();
+    (client, view, updates)
+}
+
+/// Wait for a pushed view matching `condition`
+fn wait_for_view(updates: &Receiver<ServerUpdate>, condition: impl Fn(&PlayerView) -> bool) -> PlayerView {
+    wait_for_view_with_undo(updates, condition).0
+}
+
+/// Wait for a pushed view matching `condition`, with whether the guest could
+/// undo or redo at that point
+fn wait_for_view_with_undo(
+    updates: &Receiver<ServerUpdate>,
+    condition: impl Fn(&PlayerView) -> bool,
+) -> (PlayerView, UndoAvailability) {
+    let mut undo = UndoAvailability::default();
+    loop {
+        match updates.recv_timeout(TIMEOUT).expect("no update from the host") {
+            ServerUpdate::Undo(availability) => undo = availability,
+            ServerUpdate::State(view) if condition(&view) => return (*view, undo),
+            ServerUpdate::Disconnected { error } => panic!("host disconnected: {:?}", error),
+            _ => {}
+        }
+    }
+}
+
+#[test]
+fn guest_is_seated_with_their_own_view() {
+    let (addr, host) = host_game(1);
+    let (client, view, _updates) = join(addr);
+    let (engine, remote) = host.join().unwrap();
+
+    assert_eq!(client.seat(), 2);
+    assert_eq!(remote.seat(), 2);
+    assert_eq!(view.viewer_id, 2);
+    assert_eq!(view.player2.name, "Guest");
+    assert_eq!(engine.state.player2.name, "Guest");
+
+    // The host's hand stays hidden; the guest sees their own
+    assert!(view.player1.hand.is_empty());
+    assert_eq!(view.player1.hand_size, engine.state.player1.hand.len());
+    assert_eq!(view.player2.hand.len(), engine.state.player2.hand.len());
+
+    // Only the guest knows their cards; the host holds stand-ins
+    let guest = &engine.state.player2;
+    assert!(guest.hand.iter().chain(&guest.deck).all(|card| card.is_hidden()));
+    assert_eq!(guest.hand.len() + guest.deck.len(), mock_deck().len());
+    assert!(view.player2.hand.iter().all(|card| !card.is_hidden() && mock_deck().iter().any(|c| c.id == card.id)));
+    let ids = |view: &PlayerView| view.player2.hand.iter().map(|card| card.instance_id).collect::<Vec<_>>();
+    assert_eq!(ids(&view), guest.hand.iter().map(|card| card.instance_id).collect::<Vec<_>>());
+    assert!(engine.replay().is_none());
+}
+
+#[test]
+fn host_applies_guest_actions_and_pushes_changes() {
+    let (addr, host) = host_game(2);
+    let (mut client, _, updates) = join(addr);
+    let (engine, mut remote) = host.join().unwrap();
+    let link = remote.link();
+    let engine = Arc::new(Mutex::new(engine));
+
+    let served = {
+        let engine = engine.clone();
+        thread::spawn(move || net::serve(&mut remote, &engine))
+    };
+
+    // Player 1 moves first, so the guest may not act yet
+    let result = client.act(PlayerAction::EndPhase).unwrap();
+    assert!(!result.success);
+    assert_eq!(result.error.unwrap().error, GameError::NotYourTurn);
+
+    // The host ends their turn; each change is pushed to the guest
+    {
+        let mut engine = engine.lock().unwrap();
+        while engine.state.current_player == 1 {
+            engine.process_action(1, PlayerAction::EndPhase).unwrap();
+            link.send_update(&engine).unwrap();
+        }
+    }
+    let view = wait_for_view(&updates, |view| view.current_player == 2);
+    assert_eq!(view.viewer_id, 2);
+
+    let mut phase = view.phase;
+    while phase != GamePhase::Placement {
+        let result = client.act(PlayerAction::EndPhase).unwrap();
+        assert!(result.success, "{}", result.message);
+        phase = result.new_state.unwrap().phase;
+    }
+    assert_eq!(engine.lock().unwrap().state.phase, GamePhase::Placement);
+
+    let result = client.act(PlayerAction::EndPhase).unwrap();
+    assert!(result.success, "{}", result.message);
+    assert_eq!(engine.lock().unwrap().state.phase, GamePhase::Attack);
+    let (_, undo) = wait_for_view_with_undo(&updates, |view| view.phase == GamePhase::Attack);
+    assert_eq!(undo, UndoAvailability { can_undo: true, can_redo: false });
+
+    // Undo goes through the host too
+    let result = client.undo().unwrap();
+    assert!(result.success, "{}", result.message);
+    assert_eq!(engine.lock().unwrap().state.phase, GamePhase::Placement);
+    let (_, undo) = wait_for_view_with_undo(&updates, |view| view.phase == GamePhase::Placement);
+    assert!(undo.can_redo);
+
+    // Leaving ends the host's serving loop cleanly
+    drop(client);
+    served.join().unwrap().unwrap();
+}
+
+#[test]
+fn full_game_over_the_network() {
+    let (addr, host) = host_game(3);
+    let (mut client, _, updates) = join(addr);
+    let (engine, mut remote) = host.join().unwrap();
+    let link = remote.link();
+    let engine = Arc::new(Mutex::new(engine));
+
+    let served = {
+        let engine = engine.clone();
+        thread::spawn(move || net::serve(&mut remote, &engine))
+    };
+
+    // The host's bot plays seat 1 on its side of the connection
+    let host_bot = {
+        let engine = engine.clone();
+        thread::spawn(move || {
+            let mut bot = GreedyAi::new();
+            let deadline = Instant::now() + TIMEOUT * 6;
+            while Instant::now() < deadline {
+                let mut engine = engine.lock().unwrap();
+                if let Some(winner) = engine.is_game_over() {
+                    return winner;
+                }
+                if ai::play_action(&mut engine, &mut bot, 1).is_some() {
+                    link.send_update(&engine).unwrap();
+                }
+                drop(engine);
+                thread::yield_now();
+            }
+            panic!("the game did not finish");
+        })
+    };
+
+    // The guest only sees their view, so they let every attack through and end
+    // their phases; the host's bot should win
+    let mut winner = None;
+    while winner.is_none() {
+        match updates.recv_timeout(TIMEOUT).expect("no update from the host") {
+            ServerUpdate::State(view) if view.current_player == 2 || view.pending_attack.is_some() => {
+                let acting = match &view.pending_attack {
+                    Some(pending) => pending.defending_player(),
+                    None => view.current_player,
+                };
+                if acting == 2 {
+                    let action = if view.pending_attack.is_some() {
+                        PlayerAction::PassResponse
+                    } else {
+                        PlayerAction::EndPhase
+                    };
+                    client.act(action).unwrap();
+                }
+            }
+            ServerUpdate::GameOver { winner: game_winner } => winner = Some(game_winner),
+            ServerUpdate::Disconnected { error } => panic!("host disconnected: {:?}", error),
+            _ => {}
+        }
+    }
+
+    assert_eq!(host_bot.join().unwrap(), 1);
+    assert_eq!(winner, Some(1));
+    assert_eq!(engine.lock().unwrap().is_game_over(), Some(1));
+
+    drop(client);
+    served.join().unwrap().unwrap();
+}
+
+#[test]
+fn other_protocol_versions_are_turned_away() {
+    let host = GameHost::bind("127.0.0.1:0").unwrap();
+    let addr = host.local_addr().unwrap();
+    let accepted = thread::spawn(move || host.accept().unwrap().reject("seen"));
+
+    let mut stream = TcpStream::connect(addr).unwrap();
+    let hello = format!(
+        "{{\"type\":\"Hello\",\"protocol_version\":{},\"player_name\":\"Old\"}}\n",
+        PROTOCOL_VERSION + 1
+    );
+    stream.write_all(hello.as_bytes()).unwrap();
+
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply).unwrap();
+    assert!(reply.contains("\"type\":\"Rejected\""), "{}", reply);
+    assert!(reply.contains(&format!("protocol version {}", PROTOCOL_VERSION + 1)), "{}", reply);
+
+    // The host keeps waiting for a player who speaks its version
+    let result = GameClient::connect(addr, "Guest", mock_deck(), |_| {});
+    assert_eq!(result.err(), Some(NetError::Rejected { reason: "seen".to_string() }));
+    accepted.join().unwrap().unwrap();
+}
+
+#[test]
+fn bad_connections_do_not_stop_hosting() {
+    let mut host = GameHost::bind("127.0.0.1:0").unwrap();
+    host.set_handshake_timeout(Duration::from_millis(200));
+    let addr = host.local_addr().unwrap();
+    let accepted = thread::spawn(move || host.accept().unwrap().reject("seen"));
+
+    // Garbage, a connection that closes at once and one that never says Hello
+    TcpStream::connect(addr).unwrap().write_all(b"GET / HTTP/1.1\r\n\r\n").unwrap();
+    drop(TcpStream::connect(addr).unwrap());
+    let _silent = TcpStream::connect(addr).unwrap();
+
+    let result = GameClient::connect(addr, "Guest", mock_deck(), |_| {});
+    assert_eq!(result.err(), Some(NetError::Rejected { reason: "seen".to_string() }));
+    accepted.join().unwrap().unwrap();
+}
+
+#[test]
+fn waiting_host_can_be_cancelled() {
+    let host = GameHost::bind("0.0.0.0:0").unwrap();
+    let canceller = host.canceller().unwrap();
+    let accepted = thread::spawn(move || host.accept().err());
+
+    canceller.cancel();
+    assert_eq!(accepted.join().unwrap(), Some(NetError::Cancelled));
+}
+
+#[test]
+fn unexpected_messages_end_the_connection() {
+    // A host that welcomes the guest twice
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let host = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut hello = String::new();
+        BufReader::new(stream.try_clone().unwrap()).read_line(&mut hello).unwrap();
+        let engine = GameEngine::new("Host".to_string(), "Guest".to_string());
+        let view = engine.state.view_for(2);
+        let welcome =
+            ServerMessage::Welcome { protocol_version: PROTOCOL_VERSION, seat: 2, view, sealed: vec![], dealt: vec![] };
+        for _ in 0..2 {
+            stream.write_all(format!("{}\n", serde_json::to_string(&welcome).unwrap()).as_bytes()).unwrap();
+        }
+        stream
+    });
+
+    let (_client, _, updates) = join(addr);
+    let _stream = host.join().unwrap();
+    assert!(matches!(wait_for_disconnect(&updates), Some(NetError::Protocol { .. })));
+}
+
+#[test]
+fn rejected_guest_gets_the_reason() {
+    let host = GameHost::bind("127.0.0.1:0").unwrap();
+    let addr = host.local_addr().unwrap();
+    let rejected = thread::spawn(move || host.accept().unwrap().reject("game is full"));
+
+    let result = GameClient::connect(addr, "Guest", mock_deck(), |_| {});
+    assert_eq!(result.err(), Some(NetError::Rejected { reason: "game is full".to_string() }));
+    rejected.join().unwrap().unwrap();
+}
+
+#[test]
+fn host_feigns_are_sealed_until_revealed() {
+    let (addr, host) = host_game_with(4, mock_deck(), |engine| {
+        let instance_id = ready_to_place_feign(engine);
+        engine.process_action(1, PlayerAction::PlayFeign { instance_id, column: 0 }).unwrap();
+    });
+    let (client, _, updates) = join(addr);
+    let (mut engine, remote) = host.join().unwrap();
+    let link = remote.link();
+    let view = wait_for_view(&updates, |view| view.player1.board.feigns[0].is_some());
+
+    // The guest sees a card back, never the card
+    let feign = view.player1.board.feigns[0].as_ref().unwrap();
+    assert!(feign.card.is_none());
+
+    engine.process_action(1, PlayerAction::RevealFeign { column: 0 }).unwrap();
+    link.send_update(&engine).unwrap();
+    let view = wait_for_view(&updates, |view| view.player1.board.feigns[0].as_ref().is_some_and(|f| f.is_revealed));
+    let revealed = view.player1.board.feigns[0].as_ref().unwrap().card.as_ref().unwrap();
+    assert_eq!(revealed.id, engine.state.player1.board.feigns[0].as_ref().unwrap().card.id);
+    drop(client);
+}
+
+#[test]
+fn swapping_a_face_down_feign_is_caught() {
+    let (addr, host) = host_game_with(5, mock_deck(), |engine| {
+        let instance_id = ready_to_place_feign(engine);
+        engine.process_action(1, PlayerAction::PlayFeign { instance_id, column: 0 }).unwrap();
+    });
+    let (_client, _, updates) = join(addr);
+    let (mut engine, remote) = host.join().unwrap();
+    let link = remote.link();
+    wait_for_view(&updates, |view| view.player1.board.feigns[0].is_some());
+
+    // The host quietly turns its feign into another card, then reveals it
+    let feign = engine.state.player1.board.feigns[0].as_mut().unwrap();
+    let other = CardCatalog::bundled().cards().iter()
+        .find(|card| card.card_type == CardType::Feign && card.id != feign.card.id)
+        .unwrap();
+    feign.card.card = other.clone();
+    engine.process_action(1, PlayerAction::RevealFeign { column: 0 }).unwrap();
+    link.send_update(&engine).unwrap();
+
+    assert_eq!(wait_for_disconnect(&updates), Some(NetError::CommitmentMismatch { column: 0 }));
+}
+
+#[test]
+fn guest_places_and_reveals_feigns_through_commitments() {
+    let (addr, host) = host_game_with(6, feign_deck(), |engine| ready_to_place(engine, 2));
+    let (mut client, view, _updates) = join_with(addr, feign_deck());
+    let (engine, mut remote) = host.join().unwrap();
+    let engine = Arc::new(Mutex::new(engine));
+    let served = {
+        let engine = engine.clone();
+        thread::spawn(move || net::serve(&mut remote, &engine))
+    };
+
+    let feign = view.player2.hand.iter().find(|card| card.card_type == CardType::Feign).unwrap();
+    let result = client.act(PlayerAction::PlayFeign { instance_id: feign.instance_id, column: 2 }).unwrap();
+    assert!(result.success, "{}", result.message);
+    let shown = result.new_state.unwrap();
+    assert_eq!(shown.player2.board.feigns[2].as_ref().unwrap().card.as_ref().unwrap().id, feign.id);
+
+    // The host only learns what the feign cost
+    {
+        let engine = engine.lock().unwrap();
+        let placed = &engine.state.player2.board.feigns[2].as_ref().unwrap().card;
+        assert!(placed.is_hidden());
+        assert_eq!(placed.mana_cost, feign.mana_cost);
+        assert_eq!(engine.state.player2.mana, 10 - feign.mana_cost);
+    }
+
+    let result = client.act(PlayerAction::RevealFeign { column: 2 }).unwrap();
+    assert!(result.success, "{}", result.message);
+    let revealed = engine.lock().unwrap().state.player2.board.feigns[2].clone().unwrap();
+    assert!(revealed.is_revealed);
+    assert_eq!(revealed.card.id, feign.id);
+
+    drop(client);
+    served.join().unwrap().unwrap();
+}
+
+#[test]
+fn guest_cards_are_only_shown_to_the_host_once_played() {
+    // The guest shuffles their own deck, so only creatures make sure they hold one
+    let creatures = || mock_deck().into_iter().filter(|card| card.card_type == CardType::Creature).collect::<Vec<_>>();
+    let (addr, host) = host_game_with(11, creatures(), |engine| ready_to_place(engine, 2));
+    let (mut client, view, _updates) = join_with(addr, creatures());
+    let (engine, mut remote) = host.join().unwrap();
+    let engine = Arc::new(Mutex::new(engine));
+    let served = {
+        let engine = engine.clone();
+        thread::spawn(move || net::serve(&mut remote, &engine))
+    };
+
+    let creature = view.player2.hand.iter().min_by_key(|card| card.mana_cost).unwrap();
+    let result = client.act(PlayerAction::PlayCreature { instance_id: creature.instance_id, column: 0 }).unwrap();
+    assert!(result.success, "{}", result.message);
+
+    let engine = engine.lock().unwrap();
+    assert_eq!(engine.state.player2.board.creatures[0].as_ref().unwrap().card.id, creature.id);
+    assert!(engine.state.player2.hand.iter().all(|card| card.is_hidden()));
+    drop(engine);
+
+    drop(client);
+    served.join().unwrap().unwrap();
+}
+
+/// Have the guest place a feign in column 2 of a served game, then let the
+/// host tamper with the game and send it on. Returns why the guest left, and
+/// the feign's instance id.
+fn tamper_with_guest_feign(seed: u64, tamper: impl FnOnce(&mut GameEngine, &Card)) -> (Option<NetError>, u32) {
+    let (addr, host) = host_game_with(seed, feign_deck(), |engine| ready_to_place(engine, 2));
+    let (mut client, view, updates) = join_with(addr, feign_deck());
+    let (engine, mut remote) = host.join().unwrap();
+    let link = remote.link();
+    let engine = Arc::new(Mutex::new(engine));
+    {
+        let engine = engine.clone();
+        thread::spawn(move || net::serve(&mut remote, &engine));
+    }
+
+    let feign = view.player2.hand.iter().find(|card| card.card_type == CardType::Feign).unwrap();
+    let result = client.act(PlayerAction::PlayFeign { instance_id: feign.instance_id, column: 2 }).unwrap();
+    assert!(result.success, "{}", result.message);
+    wait_for_view(&updates, |view| view.player2.board.feigns[2].is_some());
+
+    let mut engine = engine.lock().unwrap();
+    let other = CardCatalog::bundled().cards().iter()
+        .find(|card| card.card_type == CardType::Feign && card.id != feign.id)
+        .unwrap();
+    tamper(&mut engine, other);
+    link.send_update(&engine).unwrap();
+    drop(engine);
+    (wait_for_disconnect(&updates), feign.instance_id)
+}
+
+#[test]
+fn swapping_the_guests_feign_is_caught() {
+    let (error, instance_id) = tamper_with_guest_feign(9, |engine, other| {
+        engine.state.player2.board.feigns[2].as_mut().unwrap().card.card = other.clone();
+    });
+    assert_eq!(error, Some(NetError::CardMismatch { instance_id }));
+}
+
+#[test]
+fn revealing_the_guests_feign_as_another_card_is_caught() {
+    let (error, instance_id) = tamper_with_guest_feign(10, |engine, other| {
+        let feign = engine.state.player2.board.feigns[2].as_mut().unwrap();
+        feign.is_revealed = true;
+        let mut card = feign.card.clone();
+        card.card = other.clone();
+        engine.state.events.push(GameEvent::FeignRevealed { player_id: 2, card, column: 2 });
+    });
+    assert_eq!(error, Some(NetError::CardMismatch { instance_id }));
+}
+
+#[test]
+fn dealing_a_commitment_twice_is_caught() {
+    // A host that deals the guest two cards as the same commitment
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let host = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut hello = String::new();
+        BufReader::new(stream.try_clone().unwrap()).read_line(&mut hello).unwrap();
+        let engine = GameEngine::new("Host".to_string(), "Guest".to_string());
+        let dealt = vec![DealtCard { instance_id: 21, index: 0 }, DealtCard { instance_id: 22, index: 0 }];
+        let view = engine.state.view_for(2);
+        let welcome =
+            ServerMessage::Welcome { protocol_version: PROTOCOL_VERSION, seat: 2, view, sealed: vec![], dealt };
+        stream.write_all(format!("{}\n", serde_json::to_string(&welcome).unwrap()).as_bytes()).unwrap();
+        stream
+    });
+
+    let result = GameClient::connect(addr, "Guest", mock_deck(), |_| {});
+    let _stream = host.join().unwrap();
+    assert_eq!(result.err(), Some(NetError::CardMismatch { instance_id: 22 }));
+}
+
+/// Send a raw message to the host and read its reply, skipping pushed states
+fn raw_request(stream: &mut TcpStream, reader: &mut BufReader<TcpStream>, message: &str) -> ServerMessage {
+    stream.write_all(format!("{}\n", message).as_bytes()).unwrap();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        match serde_json::from_str(&line).unwrap() {
+            ServerMessage::State { .. } => continue,
+            reply => return reply,
+        }
+    }
+}
+
+type Served = JoinHandle<Result<(), NetError>>;
+
+/// A card the raw guest was dealt, with the opening of its commitment
+struct RawCard {
+    instance_id: u32,
+    card: Card,
+    opening: CardOpening,
+}
+
+/// Join a served game over a raw connection, as a guest that may break the
+/// rules, ready to place cards. The guest commits to `committed` unshuffled
+/// while the host expects a deck of `expected`. Returns the connection, the
+/// guest's hand and the game.
+fn raw_guest(
+    seed: u64,
+    committed: Vec<Card>,
+    expected: Vec<Card>,
+) -> (TcpStream, BufReader<TcpStream>, Vec<RawCard>, Arc<Mutex<GameEngine>>, Served) {
+    let (addr, host) = host_game_with(seed, expected, |engine| ready_to_place(engine, 2));
+    let mut stream = TcpStream::connect(addr).unwrap();
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let openings: Vec<_> = committed.iter().map(|card| CardOpening::new(card.id)).collect();
+    let commitments: Vec<_> = openings.iter().map(CardOpening::commitment).collect();
+    let hello = serde_json::json!({
+        "type": "Hello", "protocol_version": PROTOCOL_VERSION, "player_name": "Guest", "deck": commitments,
+    });
+    stream.write_all(format!("{}\n", hello).as_bytes()).unwrap();
+    let (engine, mut remote) = host.join().unwrap();
+    let engine = Arc::new(Mutex::new(engine));
+    let served = {
+        let engine = engine.clone();
+        thread::spawn(move || net::serve(&mut remote, &engine))
+    };
+
+    let mut line = String::new();
+    reader.read_line(&mut line).unwrap();
+    let ServerMessage::Welcome { dealt, .. } = serde_json::from_str(&line).unwrap() else { panic!("{}", line) };
+    let hand = dealt.into_iter()
+        .map(|DealtCard { instance_id, index }| RawCard {
+            instance_id,
+            card: committed[index].clone(),
+            opening: openings[index].clone(),
+        })
+        .collect();
+    (stream, reader, hand, engine, served)
+}
+
+#[test]
+fn guests_must_place_feigns_with_their_cost() {
+    let (mut stream, _reader, hand, engine, served) = raw_guest(7, feign_deck(), feign_deck());
+
+    let action = PlayerAction::PlayFeign { instance_id: hand[0].instance_id, column: 1 };
+    let bare = serde_json::json!({ "type": "Action", "action": action });
+    stream.write_all(format!("{}\n", bare).as_bytes()).unwrap();
+
+    assert!(matches!(served.join().unwrap(), Err(NetError::Protocol { .. })));
+    assert!(engine.lock().unwrap().state.player2.board.feigns[1].is_none());
+}
+
+#[test]
+fn guests_must_keep_their_commitments() {
+    // Claiming to have placed another card ends the game, as does a wrong salt
+    for wrong_card in [true, false] {
+        let (mut stream, mut reader, hand, engine, served) = raw_guest(7, feign_deck(), feign_deck());
+        let feign = &hand[0];
+        let action = PlayerAction::PlayFeign { instance_id: feign.instance_id, column: 1 };
+        let placed = serde_json::json!({ "type": "Action", "action": action, "cost": feign.card.mana_cost });
+        let ServerMessage::Result { result, .. } = raw_request(&mut stream, &mut reader, &placed.to_string()) else {
+            panic!()
+        };
+        assert!(result.success, "{}", result.message);
+
+        let bad = match wrong_card {
+            true => CardOpening { card_id: feign.card.id + 1, ..feign.opening.clone() },
+            false => CardOpening::new(feign.card.id),
+        };
+        let reveal = serde_json::json!({ "type": "Action", "action": PlayerAction::RevealFeign { column: 1 }, "opening": bad });
+        stream.write_all(format!("{}\n", reveal).as_bytes()).unwrap();
+
+        assert_eq!(served.join().unwrap(), Err(NetError::CommitmentMismatch { column: 1 }));
+        assert!(!engine.lock().unwrap().state.player2.board.feigns[1].as_ref().unwrap().is_revealed);
+        // The guest is told nothing more before the connection closes
+        let mut rest = String::new();
+        reader.read_to_string(&mut rest).unwrap();
+        assert!(rest.lines().all(|line| line.contains("\"State\"")), "{}", rest);
+    }
+}
+
+#[test]
+fn guests_cannot_play_more_copies_than_their_deck_holds() {
+    // The guest commits to a deck of nothing but Vine Sprites, of which the
+    // host's deck rules allow two
+    let sprite = CardCatalog::bundled().get(3).unwrap().clone();
+    let deck = vec![sprite.clone(); mock_deck().len()];
+    let (mut stream, mut reader, hand, engine, served) = raw_guest(8, deck, mock_deck());
+
+    for (column, card) in hand.iter().take(2).enumerate() {
+        let action = PlayerAction::PlayCreature { instance_id: card.instance_id, column };
+        let play = serde_json::json!({ "type": "Action", "action": action, "opening": card.opening });
+        let ServerMessage::Result { result, .. } = raw_request(&mut stream, &mut reader, &play.to_string()) else {
+            panic!()
+        };
+        assert!(result.success, "{}", result.message);
+    }
+    let third = &hand[2];
+    let action = PlayerAction::PlayCreature { instance_id: third.instance_id, column: 2 };
+    let play = serde_json::json!({ "type": "Action", "action": action, "opening": third.opening });
+    stream.write_all(format!("{}\n", play).as_bytes()).unwrap();
+
+    assert_eq!(served.join().unwrap(), Err(NetError::CardMismatch { instance_id: third.instance_id }));
+    let engine = engine.lock().unwrap();
+    assert!(engine.state.player2.board.creatures[2].is_none());
+    assert!(engine.state.player2.hand.iter().any(|card| card.instance_id == third.instance_id && card.is_hidden()));
+}
@@ -1,89 +1,1009 @@
-use crate::core::GameEngine;
+use crate::ai::{self, AiKind, AiPlayer};
+use crate::core::{
+    build_deck, create_mock_deck, validate_deck, CardCatalog, DeckList, DeckRules, GameEngine, Replay, ReplayError,
+    ReplayFrame, ReplayPlayer, SaveSummary, SavedAi, SavedGame,
+};
+use crate::error::{CommandError, CommandErrorView};
 use crate::models::*;
-use std::sync::Mutex;
-use tauri::State;
+use crate::net::{self, GameClient, GameHost, HostCanceller, NetError, RemoteLink, ServerUpdate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// Identifies one of the games running in the app
+pub type GameId = u64;
+
+/// Sent with a human seat's view whenever a game changes
+pub const STATE_UPDATED_EVENT: &str = "game://state-updated";
+/// Sent for every new game event, once per human seat with the event as that seat sees it
+pub const GAME_EVENT_EVENT: &str = "game://event";
+/// Sent once a change leaves a game with a winner
+pub const GAME_OVER_EVENT: &str = "game://game-over";
+/// Sent when the AI starts and stops working on its turn
+pub const AI_THINKING_EVENT: &str = "game://ai-thinking";
+/// Sent when a player joins a hosted network game and it starts
+pub const NETWORK_GAME_STARTED_EVENT: &str = "game://network-game-started";
+/// Sent when the other computer in a network game goes away
+pub const PEER_DISCONNECTED_EVENT: &str = "game://peer-disconnected";
+
+/// Payload of `STATE_UPDATED_EVENT`
+#[derive(Debug, Clone, Serialize)]
+pub struct StateUpdated {
+    pub game_id: GameId,
+    pub player_id: u32,
+    pub view: PlayerView,
+}
+
+/// Payload of `GAME_EVENT_EVENT`
+#[derive(Debug, Clone, Serialize)]
+pub struct GameEventNotice {
+    pub game_id: GameId,
+    pub player_id: u32,
+    pub index: usize, // Position of the event in the game's event list
+    pub event: GameEvent,
+}
+
+/// Payload of `GAME_OVER_EVENT`
+#[derive(Debug, Clone, Serialize)]
+pub struct GameOver {
+    pub game_id: GameId,
+    pub winner: u32,
+}
+
+/// Payload of `AI_THINKING_EVENT`
+#[derive(Debug, Clone, Serialize)]
+pub struct AiThinking {
+    pub game_id: GameId,
+    pub player_id: u32,
+    pub thinking: bool,
+}
+
+/// Payload of `PEER_DISCONNECTED_EVENT`
+#[derive(Debug, Clone, Serialize)]
+pub struct PeerDisconnected {
+    pub game_id: GameId,
+    pub reason: Option<String>, // Set when the connection failed, e.g. the host broke a feign commitment
+}
+
+/// A game just created, loaded or joined, with the local player's view of it
+/// (player 1's unless the game was joined)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewGame {
+    pub game_id: GameId,
+    pub view: PlayerView,
+}
+
+/// A running game as listed to the player
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameSummary {
+    pub game_id: GameId,
+    pub player1_name: String,
+    pub player2_name: String,
+    pub turn_number: u32,
+    pub ai: Option<SavedAi>, // The AI seated in the game, if any
+    pub winner: Option<u32>,
+    pub joined: bool, // Hosted on another computer
+}
+
+/// A network game waiting for a player to join
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostedGame {
+    pub port: u16,
+}
 
 /// Global game state managed by Tauri
 pub struct GameManager {
-    pub engine: Mutex<Option<GameEngine>>,
+    games: Mutex<BTreeMap<GameId, Arc<Mutex<GameSession>>>>,
+    joined: Mutex<BTreeMap<GameId, Arc<JoinedGame>>>, // Games hosted on other computers
+    hosting: Mutex<BTreeMap<u16, HostCanceller>>,     // Network games waiting for a player, by port
+    next_game_id: AtomicU64,
+    pub replay: Mutex<Option<ReplayPlayer>>, // Replay being watched, independent of any game
+    pub catalog: CardCatalog,
+}
+
+/// One running game and the AI or remote player seated in it, locked together
+pub struct GameSession {
+    pub engine: GameEngine,
+    pub ai: Option<AiOpponent>,
+    pub remote: Option<RemoteLink>, // Player on another computer, in hosted network games
+}
+
+/// A game hosted on another computer. The host runs the rules; this side
+/// forwards the local player's requests and keeps the latest view it was sent.
+pub struct JoinedGame {
+    client: Mutex<GameClient>,
+    latest: Arc<Mutex<JoinedView>>,
+}
+
+/// What the host of a joined game last sent
+#[derive(Default)]
+struct JoinedView {
+    view: Option<PlayerView>,
+    undo: UndoAvailability,
+    winner: Option<u32>,
+    events_seen: usize, // Events already reported to the frontend
+}
+
+/// The built-in AI seated in a game
+pub struct AiOpponent {
+    pub player_id: u32,
+    pub kind: AiKind,
+    pub player: Box<dyn AiPlayer + Send>,
+}
+
+impl AiOpponent {
+    pub fn new(player_id: u32, kind: AiKind) -> Self {
+        AiOpponent { player_id, kind, player: kind.create() }
+    }
+}
+
+impl GameSession {
+    /// The seat played by the AI, if any
+    fn ai_seat(&self) -> Option<u32> {
+        self.ai.as_ref().map(|ai| ai.player_id)
+    }
+
+    /// The seat played from another computer, if any
+    fn remote_seat(&self) -> Option<u32> {
+        self.remote.as_ref().map(RemoteLink::seat)
+    }
+
+    /// Whether a seat is played on this computer by a person
+    fn is_local_seat(&self, player_id: u32) -> bool {
+        self.ai_seat() != Some(player_id) && self.remote_seat() != Some(player_id)
+    }
+
+    /// Refuse requests made here for a seat someone else plays
+    fn check_local_seat(&self, player_id: u32) -> Result<(), CommandError> {
+        if self.ai_seat() == Some(player_id) {
+            return Err(CommandError::AiControlled { player_id });
+        }
+        if self.remote_seat() == Some(player_id) {
+            return Err(CommandError::RemoteControlled { player_id });
+        }
+        Ok(())
+    }
+
+    fn summary(&self, game_id: GameId) -> GameSummary {
+        GameSummary {
+            game_id,
+            player1_name: self.engine.state.player1.name.clone(),
+            player2_name: self.engine.state.player2.name.clone(),
+            turn_number: self.engine.state.turn_number,
+            ai: self.ai.as_ref().map(|ai| SavedAi { player_id: ai.player_id, kind: ai.kind }),
+            winner: self.engine.is_game_over(),
+            joined: false,
+        }
+    }
+}
+
+impl JoinedGame {
+    /// Forward a request for `player_id` to the host
+    fn request(
+        &self,
+        player_id: u32,
+        send: impl FnOnce(&mut GameClient) -> Result<ActionResultView, NetError>,
+    ) -> Result<ActionResultView, CommandErrorView> {
+        let mut client = self.client.lock()?;
+        if player_id != client.seat() {
+            return Err(CommandError::RemoteControlled { player_id }.into());
+        }
+        send(&mut client).map_err(|e| CommandError::from(e).into())
+    }
+
+    /// The latest view from the host
+    fn view(&self, game_id: GameId) -> Result<PlayerView, CommandError> {
+        self.latest.lock()?.view.clone().ok_or(CommandError::GameNotFound { game_id })
+    }
+
+    fn summary(&self, game_id: GameId) -> Result<GameSummary, CommandError> {
+        let latest = self.latest.lock()?;
+        let view = latest.view.as_ref().ok_or(CommandError::GameNotFound { game_id })?;
+        Ok(GameSummary {
+            game_id,
+            player1_name: view.player1.name.clone(),
+            player2_name: view.player2.name.clone(),
+            turn_number: view.turn_number,
+            ai: None,
+            winner: latest.winner,
+            joined: true,
+        })
+    }
 }
 
 impl GameManager {
-    pub fn new() -> Self {
+    pub fn new(catalog: CardCatalog) -> Self {
         GameManager {
-            engine: Mutex::new(None),
+            games: Mutex::new(BTreeMap::new()),
+            joined: Mutex::new(BTreeMap::new()),
+            hosting: Mutex::new(BTreeMap::new()),
+            next_game_id: AtomicU64::new(1),
+            replay: Mutex::new(None),
+            catalog,
         }
     }
+
+    /// Reserve an id for a new game
+    fn next_id(&self) -> GameId {
+        self.next_game_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Add a game under a fresh id
+    fn insert(&self, session: GameSession) -> Result<(GameId, Arc<Mutex<GameSession>>), CommandError> {
+        let game_id = self.next_id();
+        let session = Arc::new(Mutex::new(session));
+        self.games.lock()?.insert(game_id, session.clone());
+        Ok((game_id, session))
+    }
+
+    /// Look up a running game. The map is only locked for the lookup, so
+    /// games never wait on each other.
+    fn session(&self, game_id: GameId) -> Result<Arc<Mutex<GameSession>>, CommandError> {
+        if let Some(session) = self.games.lock()?.get(&game_id) {
+            return Ok(session.clone());
+        }
+        match self.joined.lock()?.contains_key(&game_id) {
+            true => Err(CommandError::HostedRemotely { game_id }),
+            false => Err(CommandError::GameNotFound { game_id }),
+        }
+    }
+
+    /// Look up a game hosted on another computer
+    fn joined_game(&self, game_id: GameId) -> Result<Option<Arc<JoinedGame>>, CommandError> {
+        Ok(self.joined.lock()?.get(&game_id).cloned())
+    }
 }
 
-/// Create a new game
+/// Create a new game. Passing a seed makes the deal reproducible; otherwise
+/// one is generated.
 #[tauri::command]
 pub fn create_game(
     player1_name: String,
     player2_name: String,
+    seed: Option<u64>,
+    app: AppHandle,
+    game_manager: State<GameManager>,
+) -> Result<NewGame, CommandErrorView> {
+    let engine = GameEngine::with_catalog(player1_name, player2_name, &game_manager.catalog, seed);
+    start_game(&app, &game_manager, engine, None)
+}
+
+/// Create a single-player game where player 2 is controlled by the built-in AI
+/// (the greedy AI unless another is chosen)
+#[tauri::command]
+pub fn create_ai_game(
+    player1_name: String,
+    player2_name: String,
+    ai_kind: Option<AiKind>,
+    seed: Option<u64>,
+    app: AppHandle,
     game_manager: State<GameManager>,
-) -> Result<GameState, String> {
-    let engine = GameEngine::new(player1_name, player2_name);
-    let state = engine.state.clone();
-    
-    let mut manager = game_manager.engine.lock().map_err(|e| e.to_string())?;
-    *manager = Some(engine);
-    
-    Ok(state)
+) -> Result<NewGame, CommandErrorView> {
+    let engine = GameEngine::with_catalog(player1_name, player2_name, &game_manager.catalog, seed);
+    start_game(&app, &game_manager, engine, Some(AiOpponent::new(2, ai_kind.unwrap_or_default())))
 }
 
-/// Get the current game state
+/// Create a new game from each player's deck list, rejecting illegal decks.
+/// Uses the default deck rules unless others are given.
 #[tauri::command]
-pub fn get_game_state(game_manager: State<GameManager>) -> Result<GameState, String> {
-    let manager = game_manager.engine.lock().map_err(|e| e.to_string())?;
-    
-    match &*manager {
-        Some(engine) => Ok(engine.state.clone()),
-        None => Err("No active game".to_string()),
+#[allow(clippy::too_many_arguments)] // Tauri passes each argument separately
+pub fn create_game_with_decks(
+    player1_name: String,
+    player1_deck: DeckList,
+    player2_name: String,
+    player2_deck: DeckList,
+    rules: Option<DeckRules>,
+    seed: Option<u64>,
+    app: AppHandle,
+    game_manager: State<GameManager>,
+) -> Result<NewGame, CommandErrorView> {
+    let rules = rules.unwrap_or_default();
+    let catalog = &game_manager.catalog;
+
+    for (name, deck) in [(&player1_name, &player1_deck), (&player2_name, &player2_deck)] {
+        if let Err(violations) = validate_deck(deck, catalog, &rules) {
+            return Err(CommandError::InvalidDeck {
+                player_name: name.clone(),
+                violations: violations.iter().map(|v| v.to_string()).collect(),
+            }.into());
+        }
+    }
+
+    let engine = GameEngine::with_decks(
+        player1_name,
+        build_deck(&player1_deck, catalog),
+        player2_name,
+        build_deck(&player2_deck, catalog),
+        seed,
+    );
+    start_game(&app, &game_manager, engine, None)
+}
+
+/// Register `engine` as a new game, seating `ai` if given, and report it to
+/// the frontend
+fn start_game(
+    app: &AppHandle,
+    game_manager: &GameManager,
+    engine: GameEngine,
+    ai: Option<AiOpponent>,
+) -> Result<NewGame, CommandErrorView> {
+    let (game_id, session) = game_manager.insert(GameSession { engine, ai, remote: None })?;
+    let view = {
+        let session = session.lock()?;
+        emit_update(app, game_id, &session, 0);
+        session.engine.state.view_for(1)
+    };
+
+    spawn_ai_turn(app, game_id);
+    Ok(NewGame { game_id, view })
+}
+
+/// Send a Tauri event to the frontend. Failures only mean nobody is listening,
+/// so they are logged rather than returned.
+fn emit<T: Serialize + Clone>(app: &AppHandle, event: &str, payload: T) {
+    if let Err(e) = app.emit(event, payload) {
+        eprintln!("Failed to emit {}: {}", event, e);
     }
 }
 
-/// Process a player action
+/// Tell the frontend that a game changed: every local human seat gets its
+/// view and the events recorded since `events_before`, as it may see them.
+/// A remote player's view is queued for sending over the network, so the
+/// session is never held while the network is slow.
+fn emit_update(app: &AppHandle, game_id: GameId, session: &GameSession, events_before: usize) {
+    let engine = &session.engine;
+    for (update, notices) in seat_updates(game_id, session, events_before) {
+        emit(app, STATE_UPDATED_EVENT, update);
+        for notice in notices {
+            emit(app, GAME_EVENT_EVENT, notice);
+        }
+    }
+
+    if let Some(winner) = engine.is_game_over() {
+        emit(app, GAME_OVER_EVENT, GameOver { game_id, winner });
+    }
+    if let Some(remote) = &session.remote {
+        if let Err(e) = remote.send_update(engine) {
+            eprintln!("Failed to update the remote player of game {}: {}", game_id, e);
+        }
+    }
+}
+
+/// What each local human seat is told about a change: its view, and the
+/// events recorded since `events_before` as it may see them
+fn seat_updates(
+    game_id: GameId,
+    session: &GameSession,
+    events_before: usize,
+) -> Vec<(StateUpdated, Vec<GameEventNotice>)> {
+    let state = &session.engine.state;
+    [1, 2].into_iter()
+        .filter(|&player_id| session.is_local_seat(player_id))
+        .map(|player_id| {
+            let notices = state.events.iter().enumerate().skip(events_before)
+                .map(|(index, event)| GameEventNotice { game_id, player_id, index, event: event.redacted_for(player_id) })
+                .collect();
+            (StateUpdated { game_id, player_id, view: state.view_for(player_id) }, notices)
+        })
+        .collect()
+}
+
+/// Let the AI of a game play in the background if it is its move, reporting
+/// every action as it is taken. The game is locked per action, so commands
+/// stay responsive between moves, and the AI stops if the game is closed.
+fn spawn_ai_turn(app: &AppHandle, game_id: GameId) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let game_manager = app.state::<GameManager>();
+        let mut thinking = None;
+
+        for _ in 0..ai::MAX_ACTIONS_PER_TURN {
+            let Ok(session) = game_manager.session(game_id) else { break };
+            let Ok(mut session) = session.lock() else { break };
+            let GameSession { engine, ai: Some(ai), .. } = &mut *session else { break };
+            if engine.is_game_over().is_some() || engine.state.acting_player() != ai.player_id {
+                break;
+            }
+
+            if thinking.is_none() {
+                thinking = Some(ai.player_id);
+                emit(&app, AI_THINKING_EVENT, AiThinking { game_id, player_id: ai.player_id, thinking: true });
+            }
+            let events_before = engine.state.events.len();
+            if ai::play_action(engine, ai.player.as_mut(), ai.player_id).is_none() {
+                break;
+            }
+            emit_update(&app, game_id, &session, events_before);
+        }
+
+        if let Some(player_id) = thinking {
+            emit(&app, AI_THINKING_EVENT, AiThinking { game_id, player_id, thinking: false });
+        }
+    });
+}
+
+/// Host a network game on the local network, listening on `port` (or
+/// `net::DEFAULT_PORT`). Player 1 plays here and the first player to join from
+/// another computer takes seat 2; the game starts once they do, announced by
+/// `NETWORK_GAME_STARTED_EVENT`. Until then `cancel_network_host` stops waiting.
+/// Only the joined player's computer knows their cards, so the game is closed
+/// when they leave.
+#[tauri::command]
+pub fn host_network_game(
+    player_name: String,
+    port: Option<u16>,
+    seed: Option<u64>,
+    app: AppHandle,
+    game_manager: State<GameManager>,
+) -> Result<HostedGame, CommandErrorView> {
+    let host = GameHost::bind(("0.0.0.0", port.unwrap_or(net::DEFAULT_PORT))).map_err(CommandError::from)?;
+    let port = host.local_addr().map_err(CommandError::from)?.port();
+    game_manager.hosting.lock()?.insert(port, host.canceller().map_err(CommandError::from)?);
+
+    std::thread::spawn(move || {
+        if let Err(e) = run_hosted_game(&app, host, port, player_name, seed) {
+            eprintln!("Network game on port {} ended: {}", port, e);
+        }
+    });
+    Ok(HostedGame { port })
+}
+
+/// Stop waiting for a player to join the network game hosted on `port`
 #[tauri::command]
-pub fn process_action(
+pub fn cancel_network_host(port: u16, game_manager: State<GameManager>) -> Result<(), CommandErrorView> {
+    match game_manager.hosting.lock()?.remove(&port) {
+        Some(canceller) => {
+            canceller.cancel();
+            Ok(())
+        }
+        None => Err(CommandError::NotHosting { port }.into()),
+    }
+}
+
+/// Wait for a player to join, deal the game and apply their requests until
+/// they leave or the game is closed
+fn run_hosted_game(
+    app: &AppHandle,
+    host: GameHost,
+    port: u16,
+    player_name: String,
+    seed: Option<u64>,
+) -> Result<(), CommandError> {
+    let game_manager = app.state::<GameManager>();
+    let accepted = host.accept();
+    game_manager.hosting.lock()?.remove(&port);
+    drop(host); // Nobody else may join
+    let guest = match accepted {
+        Ok(guest) => guest,
+        Err(NetError::Cancelled) => return Ok(()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let deck = || create_mock_deck(&game_manager.catalog);
+    let (engine, mut remote) = guest.deal(player_name, deck(), deck(), seed)?;
+    let link = remote.link();
+    let (game_id, session) = game_manager.insert(GameSession { engine, ai: None, remote: Some(link.clone()) })?;
+    {
+        let session = session.lock()?;
+        emit(app, NETWORK_GAME_STARTED_EVENT, NewGame { game_id, view: session.engine.state.view_for(1) });
+        emit_update(app, game_id, &session, 0);
+    }
+
+    let served: Result<(), CommandError> = (|| {
+        while let Some(request) = remote.next_request()? {
+            let Ok(session) = game_manager.session(game_id) else { break };
+            let mut session = session.lock()?;
+            let events_before = session.engine.state.events.len();
+            if link.apply(&mut session.engine, request)? {
+                emit_update(app, game_id, &session, events_before);
+            }
+        }
+        Ok(())
+    })();
+    // Only the guest knew their cards, so the game ends with them
+    link.disconnect();
+    game_manager.games.lock()?.remove(&game_id);
+    let reason = served.as_ref().err().map(|e| e.to_string());
+    emit(app, PEER_DISCONNECTED_EVENT, PeerDisconnected { game_id, reason });
+    served
+}
+
+/// Run a command that may wait on the network on a blocking thread, so a slow
+/// or unreachable host never holds up the app's main thread
+async fn run_blocking<T: Send + 'static>(
+    work: impl FnOnce() -> Result<T, CommandErrorView> + Send + 'static,
+) -> Result<T, CommandErrorView> {
+    tauri::async_runtime::spawn_blocking(work)
+        .await
+        .map_err(|e| CommandErrorView::from(CommandError::Internal { message: e.to_string() }))?
+}
+
+/// Join a network game hosted on another computer. `address` is the host's
+/// name or IP address, with the port after a colon unless it is
+/// `net::DEFAULT_PORT`.
+#[tauri::command]
+pub async fn join_network_game(address: String, player_name: String, app: AppHandle) -> Result<NewGame, CommandErrorView> {
+    run_blocking(move || {
+        let game_manager = app.state::<GameManager>();
+        let address = match address.contains(':') {
+            true => address,
+            false => format!("{}:{}", address, net::DEFAULT_PORT),
+        };
+
+        let game_id = game_manager.next_id();
+        let latest = Arc::new(Mutex::new(JoinedView::default()));
+        let on_update = {
+            let app = app.clone();
+            let latest = latest.clone();
+            move |update| report_joined_update(&app, game_id, &latest, update)
+        };
+        let deck = create_mock_deck(&game_manager.catalog);
+        let (client, view) =
+            GameClient::connect(address.as_str(), &player_name, deck, on_update).map_err(CommandError::from)?;
+
+        // An update may already have overtaken the first view
+        latest.lock()?.view.get_or_insert_with(|| view.clone());
+        let joined = JoinedGame { client: Mutex::new(client), latest };
+        game_manager.joined.lock()?.insert(game_id, Arc::new(joined));
+        Ok(NewGame { game_id, view })
+    })
+    .await
+}
+
+/// Pass on what the host of a joined game sent, as the same events a local
+/// game would produce
+fn report_joined_update(app: &AppHandle, game_id: GameId, latest: &Mutex<JoinedView>, update: ServerUpdate) {
+    let Ok(mut latest) = latest.lock() else { return };
+    match update {
+        ServerUpdate::Undo(undo) => latest.undo = undo,
+        ServerUpdate::State(view) => {
+            let player_id = view.viewer_id;
+            emit(app, STATE_UPDATED_EVENT, StateUpdated { game_id, player_id, view: (*view).clone() });
+
+            // Undo can shorten the event list
+            let events_seen = latest.events_seen.min(view.events.len());
+            for (index, event) in view.events.iter().enumerate().skip(events_seen) {
+                emit(app, GAME_EVENT_EVENT, GameEventNotice { game_id, player_id, index, event: event.clone() });
+            }
+            latest.events_seen = view.events.len();
+            latest.view = Some(*view);
+        }
+        ServerUpdate::GameOver { winner } => {
+            latest.winner = Some(winner);
+            emit(app, GAME_OVER_EVENT, GameOver { game_id, winner });
+        }
+        ServerUpdate::Disconnected { error } => {
+            let reason = error.map(|e| e.to_string());
+            emit(app, PEER_DISCONNECTED_EVENT, PeerDisconnected { game_id, reason });
+        }
+    }
+}
+
+/// List the running games, oldest first
+#[tauri::command]
+pub fn list_games(game_manager: State<GameManager>) -> Result<Vec<GameSummary>, CommandErrorView> {
+    let games: Vec<_> = game_manager.games.lock()?.iter()
+        .map(|(game_id, session)| (*game_id, session.clone()))
+        .collect();
+    let joined: Vec<_> = game_manager.joined.lock()?.iter()
+        .map(|(game_id, joined)| (*game_id, joined.clone()))
+        .collect();
+
+    let mut summaries = Vec::new();
+    for (game_id, session) in games {
+        summaries.push(session.lock()?.summary(game_id));
+    }
+    for (game_id, joined) in joined {
+        summaries.push(joined.summary(game_id)?);
+    }
+    summaries.sort_by_key(|summary| summary.game_id);
+    Ok(summaries)
+}
+
+/// End a game and forget it. Network games are disconnected.
+#[tauri::command]
+pub fn close_game(game_id: GameId, game_manager: State<GameManager>) -> Result<(), CommandErrorView> {
+    if let Some(session) = game_manager.games.lock()?.remove(&game_id) {
+        if let Some(remote) = &session.lock()?.remote {
+            remote.disconnect();
+        }
+        return Ok(());
+    }
+    // Dropping the client closes its connection
+    match game_manager.joined.lock()?.remove(&game_id) {
+        Some(_) => Ok(()),
+        None => Err(CommandError::GameNotFound { game_id }.into()),
+    }
+}
+
+/// Get the current state of a game as seen by a player. Only seats played on
+/// this computer can be seen, so the AI's or remote player's hand stays hidden.
+#[tauri::command]
+pub fn get_game_state(game_id: GameId, player_id: u32, game_manager: State<GameManager>) -> Result<PlayerView, CommandErrorView> {
+    if let Some(joined) = game_manager.joined_game(game_id)? {
+        let view = joined.view(game_id)?;
+        if view.viewer_id != player_id {
+            return Err(CommandError::RemoteControlled { player_id }.into());
+        }
+        return Ok(view);
+    }
+    let session = game_manager.session(game_id)?;
+    let session = session.lock()?;
+    session.check_local_seat(player_id)?;
+    Ok(session.engine.state.view_for(player_id))
+}
+
+/// Process a player action, returning the result as seen by that player.
+/// In games against the AI, the AI then takes its turn in the background and
+/// its moves arrive as events. Actions in joined games are sent to the host.
+#[tauri::command]
+pub async fn process_action(
+    game_id: GameId,
     player_id: u32,
     action: PlayerAction,
+    app: AppHandle,
+) -> Result<ActionResultView, CommandErrorView> {
+    run_blocking(move || {
+        let game_manager = app.state::<GameManager>();
+        if let Some(joined) = game_manager.joined_game(game_id)? {
+            return joined.request(player_id, |client| client.act(action));
+        }
+        let session = game_manager.session(game_id)?;
+        let mut session = session.lock()?;
+        session.check_local_seat(player_id)?;
+
+        let events_before = session.engine.state.events.len();
+        let result = session.engine.process_action(player_id, action);
+        if result.is_ok() {
+            emit_update(&app, game_id, &session, events_before);
+            spawn_ai_turn(&app, game_id);
+        }
+        Ok(ActionResultView::new(&result, player_id))
+    })
+    .await
+}
+
+/// Take back a player's last action this turn
+#[tauri::command]
+pub async fn undo_action(
+    game_id: GameId,
+    player_id: u32,
+    app: AppHandle,
+) -> Result<ActionResultView, CommandErrorView> {
+    run_blocking(move || {
+        let game_manager = app.state::<GameManager>();
+        if let Some(joined) = game_manager.joined_game(game_id)? {
+            return joined.request(player_id, GameClient::undo);
+        }
+        let session = game_manager.session(game_id)?;
+        let mut session = session.lock()?;
+        session.check_local_seat(player_id)?;
+
+        let result = session.engine.undo(player_id);
+        if result.is_ok() {
+            emit_update(&app, game_id, &session, session.engine.state.events.len());
+        }
+        Ok(ActionResultView::new(&result, player_id))
+    })
+    .await
+}
+
+/// Repeat a player's last undone action
+#[tauri::command]
+pub async fn redo_action(
+    game_id: GameId,
+    player_id: u32,
+    app: AppHandle,
+) -> Result<ActionResultView, CommandErrorView> {
+    run_blocking(move || {
+        let game_manager = app.state::<GameManager>();
+        if let Some(joined) = game_manager.joined_game(game_id)? {
+            return joined.request(player_id, GameClient::redo);
+        }
+        let session = game_manager.session(game_id)?;
+        let mut session = session.lock()?;
+        session.check_local_seat(player_id)?;
+
+        let events_before = session.engine.state.events.len();
+        let result = session.engine.redo(player_id);
+        if result.is_ok() {
+            emit_update(&app, game_id, &session, events_before);
+            spawn_ai_turn(&app, game_id);
+        }
+        Ok(ActionResultView::new(&result, player_id))
+    })
+    .await
+}
+
+/// Check whether a player can currently undo or redo. In joined games the
+/// host sends this with every change.
+#[tauri::command]
+pub fn get_undo_availability(
+    game_id: GameId,
+    player_id: u32,
     game_manager: State<GameManager>,
-) -> Result<ActionResult, String> {
-    let mut manager = game_manager.engine.lock().map_err(|e| e.to_string())?;
-    
-    match &mut *manager {
-        Some(engine) => Ok(engine.process_action(player_id, action)),
-        None => Err("No active game".to_string()),
+) -> Result<UndoAvailability, CommandErrorView> {
+    if let Some(joined) = game_manager.joined_game(game_id)? {
+        if joined.view(game_id)?.viewer_id != player_id {
+            return Err(CommandError::RemoteControlled { player_id }.into());
+        }
+        return Ok(joined.latest.lock()?.undo.clone());
     }
+    let session = game_manager.session(game_id)?;
+    let session = session.lock()?;
+    session.check_local_seat(player_id)?;
+    Ok(session.engine.undo_availability(player_id))
 }
 
-/// Check if the game is over and who won
+/// List every action a player may take right now
 #[tauri::command]
-pub fn check_game_over(game_manager: State<GameManager>) -> Result<Option<u32>, String> {
-    let manager = game_manager.engine.lock().map_err(|e| e.to_string())?;
-    
-    match &*manager {
-        Some(engine) => Ok(engine.is_game_over()),
-        None => Err("No active game".to_string()),
+pub fn get_legal_actions(
+    game_id: GameId,
+    player_id: u32,
+    game_manager: State<GameManager>,
+) -> Result<Vec<PlayerAction>, CommandErrorView> {
+    let session = game_manager.session(game_id)?;
+    let session = session.lock()?;
+    session.check_local_seat(player_id)?;
+    Ok(session.engine.legal_actions(player_id))
+}
+
+/// Check if a game is over and who won
+#[tauri::command]
+pub fn check_game_over(game_id: GameId, game_manager: State<GameManager>) -> Result<Option<u32>, CommandErrorView> {
+    if let Some(joined) = game_manager.joined_game(game_id)? {
+        return Ok(joined.latest.lock()?.winner);
     }
+    let session = game_manager.session(game_id)?;
+    let session = session.lock()?;
+    Ok(session.engine.is_game_over())
 }
 
-/// Get the game log
+/// Get the log of a game
 #[tauri::command]
-pub fn get_game_log(game_manager: State<GameManager>) -> Result<Vec<String>, String> {
-    let manager = game_manager.engine.lock().map_err(|e| e.to_string())?;
-    
-    match &*manager {
-        Some(engine) => Ok(engine.state.game_log.clone()),
-        None => Err("No active game".to_string()),
+pub fn get_game_log(game_id: GameId, game_manager: State<GameManager>) -> Result<Vec<String>, CommandErrorView> {
+    if let Some(joined) = game_manager.joined_game(game_id)? {
+        return Ok(joined.view(game_id)?.game_log);
     }
+    let session = game_manager.session(game_id)?;
+    let session = session.lock()?;
+    Ok(session.engine.state.game_log())
+}
+
+/// Get every card definition in the catalog
+#[tauri::command]
+pub fn get_card_catalog(game_manager: State<GameManager>) -> Vec<Card> {
+    game_manager.catalog.cards().to_vec()
 }
 
-/// Reset the game
+/// Render a deck list in the shareable text format
 #[tauri::command]
-pub fn reset_game(game_manager: State<GameManager>) -> Result<String, String> {
-    let mut manager = game_manager.engine.lock().map_err(|e| e.to_string())?;
-    *manager = None;
-    Ok("Game reset".to_string())
-} 
\ No newline at end of file
+pub fn export_deck_text(deck: DeckList, game_manager: State<GameManager>) -> String {
+    deck.to_text(&game_manager.catalog)
+}
+
+/// Encode a deck list as a compact deck code
+#[tauri::command]
+pub fn export_deck_code(deck: DeckList) -> String {
+    deck.to_code()
+}
+
+/// Parse a deck from either the text format or a deck code
+#[tauri::command]
+pub fn import_deck(input: String, game_manager: State<GameManager>) -> Result<DeckList, CommandErrorView> {
+    DeckList::import(&input, &game_manager.catalog).map_err(|errors| {
+        CommandError::InvalidDeckImport { problems: errors.iter().map(|e| e.to_string()).collect() }.into()
+    })
+}
+
+/// Write a finished game's replay to a file. Replays hold every hand and
+/// face-down feign, so they are only given out once the game is over.
+#[tauri::command]
+pub fn save_replay(game_id: GameId, path: String, game_manager: State<GameManager>) -> Result<(), CommandErrorView> {
+    let session = game_manager.session(game_id)?;
+    let session = session.lock()?;
+    if session.engine.is_game_over().is_none() {
+        return Err(CommandError::GameInProgress { game_id }.into());
+    }
+    let replay = session.engine.replay().ok_or(CommandError::GameNotRecorded)?;
+    std::fs::write(&path, replay.to_json()).map_err(|e| io_error(&path, e).into())
+}
+
+/// Open a replay file for watching, positioned before the first action
+#[tauri::command]
+pub fn load_replay(path: String, game_manager: State<GameManager>) -> Result<ReplayFrame, CommandErrorView> {
+    let text = std::fs::read_to_string(&path).map_err(|e| io_error(&path, e))?;
+    let replay = Replay::from_json(&text).map_err(|e| invalid_file(&path, e))?;
+
+    let player = ReplayPlayer::new(replay);
+    let frame = player.frame();
+    *game_manager.replay.lock()? = Some(player);
+    Ok(frame)
+}
+
+/// Apply the next action of the open replay
+#[tauri::command]
+pub fn replay_step_forward(game_manager: State<GameManager>) -> Result<ReplayFrame, CommandErrorView> {
+    let mut replay = game_manager.replay.lock()?;
+
+    match &mut *replay {
+        Some(player) => player.step_forward().map_err(replay_error),
+        None => Err(CommandError::NoReplayLoaded.into()),
+    }
+}
+
+/// Undo the last applied action of the open replay
+#[tauri::command]
+pub fn replay_step_backward(game_manager: State<GameManager>) -> Result<ReplayFrame, CommandErrorView> {
+    let mut replay = game_manager.replay.lock()?;
+
+    match &mut *replay {
+        Some(player) => Ok(player.step_backward()),
+        None => Err(CommandError::NoReplayLoaded.into()),
+    }
+}
+
+/// Jump the open replay to the position after `position` actions
+#[tauri::command]
+pub fn replay_seek(position: usize, game_manager: State<GameManager>) -> Result<ReplayFrame, CommandErrorView> {
+    let mut replay = game_manager.replay.lock()?;
+
+    match &mut *replay {
+        Some(player) => player.seek(position).map_err(replay_error),
+        None => Err(CommandError::NoReplayLoaded.into()),
+    }
+}
+
+/// Error for a file that could not be read or written
+fn io_error(path: impl AsRef<Path>, e: std::io::Error) -> CommandError {
+    CommandError::Io { path: path.as_ref().display().to_string(), message: e.to_string() }
+}
+
+/// Error for a file that was read but could not be understood
+fn invalid_file(path: impl AsRef<Path>, e: impl std::fmt::Display) -> CommandError {
+    CommandError::InvalidFile { path: path.as_ref().display().to_string(), message: e.to_string() }
+}
+
+/// Error for a replay whose recorded actions no longer apply
+fn replay_error(e: ReplayError) -> CommandErrorView {
+    CommandError::ReplayDiverged { message: e.to_string() }.into()
+}
+
+/// Folder in the app data directory holding saved games
+fn saves_dir(app: &AppHandle) -> Result<PathBuf, CommandError> {
+    let dir = app.path().app_data_dir().map_err(|e| CommandError::Internal { message: e.to_string() })?;
+    Ok(dir.join("saves"))
+}
+
+/// Path of a saved game, rejecting ids that could point outside the saves folder
+fn save_path(app: &AppHandle, id: &str) -> Result<PathBuf, CommandError> {
+    let valid = !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(CommandError::InvalidSaveId { id: id.to_string() });
+    }
+    Ok(saves_dir(app)?.join(format!("{}.json", id)))
+}
+
+/// Save a game to a new file in the saves folder
+#[tauri::command]
+pub fn save_game(game_id: GameId, app: AppHandle, game_manager: State<GameManager>) -> Result<SaveSummary, CommandErrorView> {
+    let session = game_manager.session(game_id)?;
+    let session = session.lock()?;
+    if session.remote.is_some() {
+        return Err(CommandError::NetworkGameNotSaved { game_id }.into());
+    }
+
+    let saved_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| CommandError::Internal { message: e.to_string() })?
+        .as_secs();
+    let saved_ai = session.ai.as_ref().map(|ai| SavedAi { player_id: ai.player_id, kind: ai.kind });
+    let saved = SavedGame::new(session.engine.snapshot(), saved_ai, saved_at);
+
+    let dir = saves_dir(&app)?;
+    std::fs::create_dir_all(&dir).map_err(|e| io_error(&dir, e))?;
+    let mut id = format!("game-{}", saved_at);
+    let mut suffix = 1;
+    while save_path(&app, &id)?.exists() {
+        suffix += 1;
+        id = format!("game-{}-{}", saved_at, suffix);
+    }
+
+    let path = save_path(&app, &id)?;
+    std::fs::write(&path, saved.to_json()).map_err(|e| io_error(&path, e))?;
+    Ok(SaveSummary { id, metadata: saved.metadata })
+}
+
+/// List saved games, newest first. Unreadable files are skipped.
+#[tauri::command]
+pub fn list_saved_games(app: AppHandle) -> Result<Vec<SaveSummary>, CommandErrorView> {
+    let dir = saves_dir(&app)?;
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let entries = std::fs::read_dir(&dir).map_err(|e| io_error(&dir, e))?;
+    let mut saves = Vec::new();
+    for path in entries.filter_map(|entry| entry.ok().map(|entry| entry.path())) {
+        let Some(id) = path.file_stem().and_then(|stem| stem.to_str()) else { continue };
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+
+        let metadata = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| SavedGame::metadata_from_json(&text).map_err(|e| e.to_string()));
+        match metadata {
+            Ok(metadata) => saves.push(SaveSummary { id: id.to_string(), metadata }),
+            Err(e) => eprintln!("Skipping saved game {}: {}", path.display(), e),
+        }
+    }
+    saves.sort_by_key(|save| std::cmp::Reverse(save.metadata.saved_at));
+    Ok(saves)
+}
+
+/// Resume a saved game as a new running game
+#[tauri::command]
+pub fn load_game(id: String, app: AppHandle, game_manager: State<GameManager>) -> Result<NewGame, CommandErrorView> {
+    let path = save_path(&app, &id)?;
+    let text = std::fs::read_to_string(&path).map_err(|e| io_error(&path, e))?;
+    let saved = SavedGame::from_json(&text).map_err(|e| invalid_file(&path, e))?;
+
+    let engine = GameEngine::restore(saved.engine);
+    start_game(&app, &game_manager, engine, saved.ai.map(|ai| AiOpponent::new(ai.player_id, ai.kind)))
+}
+
+/// Delete a saved game
+#[tauri::command]
+pub fn delete_saved_game(id: String, app: AppHandle) -> Result<(), CommandErrorView> {
+    let path = save_path(&app, &id)?;
+    std::fs::remove_file(&path).map_err(|e| io_error(&path, e).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(ai: Option<AiOpponent>) -> GameSession {
+        let engine = GameEngine::with_catalog("Alice".to_string(), "Bob".to_string(), CardCatalog::bundled(), Some(1));
+        GameSession { engine, ai, remote: None }
+    }
+
+    #[test]
+    fn only_human_seats_are_updated() {
+        let mut session = session(Some(AiOpponent::new(2, AiKind::Greedy)));
+        let events_before = session.engine.state.events.len();
+        session.engine.process_action(1, PlayerAction::EndPhase).unwrap();
+
+        let updates = seat_updates(7, &session, events_before);
+
+        assert_eq!(updates.len(), 1);
+        let (update, notices) = &updates[0];
+        assert_eq!((update.game_id, update.player_id, update.view.viewer_id), (7, 1, 1));
+        let indices: Vec<_> = notices.iter().map(|notice| notice.index).collect();
+        assert_eq!(indices, (events_before..session.engine.state.events.len()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn each_seat_is_sent_events_as_it_may_see_them() {
+        let mut session = session(None);
+        let events_before = session.engine.state.events.len();
+        session.engine.process_action(1, PlayerAction::EndPhase).unwrap();
+
+        let updates = seat_updates(7, &session, events_before);
+
+        assert_eq!(updates.iter().map(|(update, _)| update.player_id).collect::<Vec<_>>(), vec![1, 2]);
+        let drawn = |notices: &[GameEventNotice]| {
+            notices.iter().find_map(|notice| match notice.event {
+                GameEvent::CardDrawn { player_id: 1, instance_id } => Some(instance_id),
+                _ => None,
+            })
+        };
+        assert!(matches!(drawn(&updates[0].1), Some(Some(_))));
+        assert_eq!(drawn(&updates[1].1), Some(None));
+    }
+}
@@ -1,7 +0,0 @@
-pub mod game_engine;
-pub mod combat;
-pub mod deck_builder;
-
-pub use game_engine::GameEngine;
-pub use combat::CombatResolver;
-pub use deck_builder::create_mock_deck; 
\ No newline at end of file
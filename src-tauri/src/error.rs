@@ -0,0 +1,112 @@
+use crate::models::GameError;
+use crate::net::NetError;
+use serde::Serialize;
+use std::fmt;
+
+/// Why a command was rejected: either the engine refused an action, or the
+/// command itself failed around it. Serialized exactly like `GameError`, with
+/// a `code` naming the variant, so the frontend sees one set of codes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "code", content = "details")]
+pub enum CommandError {
+    GameNotFound { game_id: u64 },
+    AiControlled { player_id: u32 },
+    RemoteControlled { player_id: u32 },
+    HostedRemotely { game_id: u64 },
+    NotHosting { port: u16 },
+    InvalidDeck { player_name: String, violations: Vec<String> },
+    InvalidDeckImport { problems: Vec<String> },
+    GameNotRecorded,
+    GameInProgress { game_id: u64 },
+    NoReplayLoaded,
+    ReplayDiverged { message: String },
+    InvalidSaveId { id: String },
+    NetworkGameNotSaved { game_id: u64 },
+    InvalidFile { path: String, message: String },
+    Io { path: String, message: String },
+    Network(NetError), // `details` holds the connection error's own code and details
+    Internal { message: String },
+
+    // The engine's own errors keep their codes
+    #[serde(untagged)]
+    Game(GameError),
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandError::GameNotFound { game_id } => write!(f, "No game with id {}", game_id),
+            CommandError::AiControlled { player_id } => write!(f, "Player {} is controlled by the AI", player_id),
+            CommandError::RemoteControlled { player_id } => {
+                write!(f, "Player {} is playing from another computer", player_id)
+            }
+            CommandError::HostedRemotely { game_id } => write!(f, "Game {} is run by the computer hosting it", game_id),
+            CommandError::NotHosting { port } => write!(f, "No game is waiting for a player on port {}", port),
+            CommandError::InvalidDeck { player_name, violations } => {
+                write!(f, "{}'s deck is illegal: {}", player_name, violations.join("; "))
+            }
+            CommandError::InvalidDeckImport { problems } => write!(f, "{}", problems.join("\n")),
+            CommandError::GameNotRecorded => write!(f, "This game is not being recorded"),
+            CommandError::GameInProgress { game_id } => {
+                write!(f, "Game {} is not over yet; its replay would show hidden cards", game_id)
+            }
+            CommandError::NoReplayLoaded => write!(f, "No replay loaded"),
+            CommandError::ReplayDiverged { message } => write!(f, "{}", message),
+            CommandError::InvalidSaveId { id } => write!(f, "Invalid save id: {}", id),
+            CommandError::NetworkGameNotSaved { game_id } => {
+                write!(f, "Game {} is played over the network, so only the guest's computer knows their cards", game_id)
+            }
+            CommandError::InvalidFile { path, message } => write!(f, "{}: {}", path, message),
+            CommandError::Io { path, message } => write!(f, "{}: {}", path, message),
+            CommandError::Network(error) => write!(f, "{}", error),
+            CommandError::Internal { message } => write!(f, "Internal error: {}", message),
+            CommandError::Game(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+impl From<GameError> for CommandError {
+    fn from(e: GameError) -> Self {
+        CommandError::Game(e)
+    }
+}
+
+impl From<NetError> for CommandError {
+    fn from(e: NetError) -> Self {
+        CommandError::Network(e)
+    }
+}
+
+impl<T> From<std::sync::PoisonError<T>> for CommandError {
+    fn from(e: std::sync::PoisonError<T>) -> Self {
+        CommandError::Internal { message: e.to_string() }
+    }
+}
+
+/// A command error as sent to the frontend: its code and details plus the message
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandErrorView {
+    #[serde(flatten)]
+    pub error: CommandError,
+    pub message: String,
+}
+
+impl From<CommandError> for CommandErrorView {
+    fn from(error: CommandError) -> Self {
+        CommandErrorView { message: error.to_string(), error }
+    }
+}
+
+impl From<GameError> for CommandErrorView {
+    fn from(error: GameError) -> Self {
+        CommandError::from(error).into()
+    }
+}
+
+impl<T> From<std::sync::PoisonError<T>> for CommandErrorView {
+    fn from(e: std::sync::PoisonError<T>) -> Self {
+        CommandError::from(e).into()
+    }
+}
@@ -1,22 +1,56 @@
-// Core game modules
-pub mod models;
-pub mod core;
+// The rules live in the engine crate; this crate adapts them to Tauri
+pub use feign_engine::{ai, core, models, net};
 pub mod commands;
+pub mod error;
 
 use commands::GameManager;
+use crate::core::CardCatalog;
+use tauri::Manager;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
-        .manage(GameManager::new())
+        .setup(|app| {
+            // Players can add their own cards as JSON files in <app data>/cards
+            let user_cards = app.path().app_data_dir()?.join("cards");
+            let catalog = CardCatalog::bundled_with_user_dir(&user_cards).unwrap_or_else(|e| {
+                eprintln!("Ignoring user card data: {}", e);
+                CardCatalog::bundled().clone()
+            });
+            app.manage(GameManager::new(catalog));
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             commands::create_game,
+            commands::create_ai_game,
+            commands::create_game_with_decks,
+            commands::list_games,
+            commands::close_game,
+            commands::host_network_game,
+            commands::cancel_network_host,
+            commands::join_network_game,
             commands::get_game_state,
             commands::process_action,
+            commands::get_legal_actions,
+            commands::undo_action,
+            commands::redo_action,
+            commands::get_undo_availability,
             commands::check_game_over,
             commands::get_game_log,
-            commands::reset_game,
+            commands::get_card_catalog,
+            commands::export_deck_text,
+            commands::export_deck_code,
+            commands::import_deck,
+            commands::save_replay,
+            commands::load_replay,
+            commands::replay_step_forward,
+            commands::replay_step_backward,
+            commands::replay_seek,
+            commands::save_game,
+            commands::list_saved_games,
+            commands::load_game,
+            commands::delete_saved_game,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
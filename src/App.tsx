@@ -9,17 +9,22 @@ function App() {
     gameState,
     loading,
     error,
+    winner,
+    viewerId,
+    hostingPort,
+    peerDisconnected,
     createGame,
+    hostGame,
+    joinGame,
     processAction,
-    checkGameOver,
     resetGame,
   } = useGameState();
 
   const [gameLog, setGameLog] = useState<string[]>([]);
-  const [winner, setWinner] = useState<number | null>(null);
   const [showSetup, setShowSetup] = useState(true);
   const [player1Name, setPlayer1Name] = useState('Player 1');
   const [player2Name, setPlayer2Name] = useState('Player 2');
+  const [hostAddress, setHostAddress] = useState('');
 
   // Update game log when game state changes
   useEffect(() => {
@@ -28,32 +33,27 @@ function App() {
     }
   }, [gameState]);
 
-  // Check for game over
-  useEffect(() => {
-    const checkWinner = async () => {
-      if (gameState) {
-        try {
-          const gameWinner = await checkGameOver();
-          setWinner(gameWinner);
-        } catch (err) {
-          console.error('Failed to check game over:', err);
-        }
-      }
-    };
-    checkWinner();
-  }, [gameState, checkGameOver]);
-
   const handleCreateGame = async () => {
     try {
       await createGame(player1Name, player2Name);
       setShowSetup(false);
-      setWinner(null);
     } catch (err) {
       console.error('Failed to create game:', err);
       alert('Failed to create game. Please make sure the Tauri backend is running.');
     }
   };
 
+  // Network games: player 1's name is the local player's
+  const handleHostGame = async () => {
+    await hostGame(player1Name);
+    setShowSetup(false);
+  };
+
+  const handleJoinGame = async () => {
+    await joinGame(hostAddress.trim(), player1Name);
+    setShowSetup(false);
+  };
+
   const handleAction = async (playerId: number, action: PlayerAction) => {
     try {
       const result = await processAction(playerId, action);
@@ -72,7 +72,6 @@ function App() {
     try {
       await resetGame();
       setShowSetup(true);
-      setWinner(null);
       setGameLog([]);
     } catch (err) {
       console.error('Failed to reset game:', err);
@@ -120,6 +119,22 @@ function App() {
     );
   }
 
+  if (hostingPort !== null && !gameState) {
+    return (
+      <div className="setup-container">
+        <div className="setup-card">
+          <h1 className="setup-title">Waiting for a Player</h1>
+          <p className="setup-subtitle">
+            Hosting on port {hostingPort}. Other players on your network can join with this computer's address.
+          </p>
+          <button onClick={handleResetGame} className="start-game-btn mt-6">
+            Cancel
+          </button>
+        </div>
+      </div>
+    );
+  }
+
   if (showSetup || !gameState) {
     return (
       <div className="setup-container">
@@ -164,6 +179,37 @@ function App() {
               Start Game
             </button>
           </div>
+
+          <div className="space-y-4 mt-6">
+            <h3 className="font-semibold text-slate-200">Network Play</h3>
+            <button
+              onClick={handleHostGame}
+              disabled={!player1Name.trim()}
+              className="start-game-btn"
+            >
+              Host LAN Game
+            </button>
+
+            <div className="form-group">
+              <label className="form-label">
+                Host Address
+              </label>
+              <input
+                type="text"
+                value={hostAddress}
+                onChange={(e) => setHostAddress(e.target.value)}
+                className="form-input"
+                placeholder="e.g. 192.168.1.20 or 192.168.1.20:7878"
+              />
+            </div>
+            <button
+              onClick={handleJoinGame}
+              disabled={!player1Name.trim() || !hostAddress.trim()}
+              className="start-game-btn"
+            >
+              Join LAN Game
+            </button>
+          </div>
           
           <div className="mt-6 text-sm text-slate-300">
             <h3 className="font-semibold mb-2">How to Play:</h3>
@@ -202,12 +248,17 @@ function App() {
 
   return (
     <div className="h-screen bg-gray-100">
+      {peerDisconnected && (
+        <div className="bg-yellow-100 border border-yellow-400 text-yellow-800 px-4 py-2 text-center">
+          The other player disconnected.
+        </div>
+      )}
       {/* Full Width Game Area */}
       <div className="h-full overflow-auto">
         <GameBoard
           gameState={gameState}
           onAction={handleAction}
-          currentPlayerId={1} // For now, always show from Player 1's perspective
+          currentPlayerId={viewerId}
           onResetGame={handleResetGame}
         />
       </div>
@@ -1,12 +1,12 @@
-import React, { useState, useEffect } from 'react';
+import React, { useState } from 'react';
 import { CreatureCard } from './cards/CreatureCard';
 import { FeignCard } from './cards/FeignCard';
 import { HandCard } from './cards/HandCard';
-import type { GameState, Card, PlayerAction } from '../types/game';
+import type { PlayerView, Card, CardInstance, PlayerAction } from '../types/game';
 import { CardType, GamePhase, Color } from '../types/game';
 
 interface GameBoardProps {
-  gameState: GameState;
+  gameState: PlayerView;
   onAction: (playerId: number, action: PlayerAction) => Promise<void>;
   currentPlayerId: number; // Which player is viewing (1 or 2)
   onResetGame: () => Promise<void>; // Add reset game functionality
@@ -33,109 +33,35 @@ export const GameBoard: React.FC<GameBoardProps> = ({
   currentPlayerId,
   onResetGame,
 }) => {
-  const [selectedCard, setSelectedCard] = useState<Card | null>(null);
+  const [selectedCard, setSelectedCard] = useState<CardInstance | null>(null);
 
   const currentPlayer = currentPlayerId === 1 ? gameState.player1 : gameState.player2;
   const opponentPlayer = currentPlayerId === 1 ? gameState.player2 : gameState.player1;
   const isCurrentPlayerTurn = gameState.current_player === currentPlayerId;
+  const isRespondingToAttack = gameState.pending_attack !== null
+    && gameState.pending_attack.attacking_player !== currentPlayerId;
 
-  // Basic AI for Player 2
-  useEffect(() => {
-    const makeAIMove = async () => {
-      if (gameState.current_player === 2 && gameState.phase === GamePhase.Placement) {
-        // Wait a short delay to make the AI feel more natural
-        await new Promise(resolve => setTimeout(resolve, 1500));
-        
-        const player2 = gameState.player2;
-        
-        // Find playable creatures
-        const playableCreatures = player2.hand.filter(card => 
-          card.card_type === CardType.Creature && card.mana_cost <= player2.mana
-        );
-        
-                if (playableCreatures.length > 0) {
-          try {
-            const selectedCreature = playableCreatures[0]; // Play the first affordable creature
-            await onAction(2, { PlayCreature: { card_id: selectedCreature.id } });
-            console.log(`AI played ${selectedCreature.name}`);
-            
-            // After a short delay, end the phase
-            setTimeout(async () => {
-              await onAction(2, "EndPhase");
-            }, 1000);
-          } catch (error) {
-            console.error('AI failed to play creature:', error);
-            // If creature play fails, just end the phase
-            setTimeout(async () => {
-              await onAction(2, "EndPhase");
-            }, 500);
-          }
-        } else {
-          // No playable creatures, end phase
-          setTimeout(async () => {
-            await onAction(2, "EndPhase");
-          }, 1000);
-        }
-      } else if (gameState.current_player === 2 && gameState.phase === GamePhase.Draw) {
-        // Auto-draw for AI
-        setTimeout(async () => {
-          await onAction(2, "EndPhase");
-        }, 800);
-      } else if (gameState.current_player === 2 && gameState.phase === GamePhase.Attack) {
-        // Auto-attack with any available creatures
-        setTimeout(async () => {
-          const player2 = gameState.player2;
-          const availableCreatures = player2.board.creatures
-            .map((creature, index) => ({ creature, index }))
-            .filter(({ creature }) => creature !== null);
-          
-          if (availableCreatures.length > 0) {
-            try {
-              // Attack with the first creature
-                             await onAction(2, { Attack: { creature_index: availableCreatures[0].index } });
-              console.log(`AI attacked with creature at index ${availableCreatures[0].index}`);
-            } catch (error) {
-              console.error('AI failed to attack:', error);
-            }
-          }
-          
-          // End attack phase after attempting to attack
-          setTimeout(async () => {
-            await onAction(2, "EndPhase");
-          }, 1000);
-        }, 1200);
-      } else if (gameState.current_player === 2 && gameState.phase === GamePhase.EndTurn) {
-        // Auto-end turn for AI
-        setTimeout(async () => {
-          await onAction(2, "EndPhase");
-        }, 500);
-      }
-    };
-    
-    makeAIMove();
-  }, [gameState.current_player, gameState.phase, gameState.turn_number]);
-
-  const handleCardSelect = (card: Card) => {
+  const handleCardSelect = (card: CardInstance) => {
     if (!isCurrentPlayerTurn) return;
-    setSelectedCard(selectedCard?.id === card.id ? null : card);
+    setSelectedCard(selectedCard?.instance_id === card.instance_id ? null : card);
   };
 
-  const handlePlayCreature = async () => {
+  const handlePlayCreature = async (column: number) => {
     if (!selectedCard || selectedCard.card_type !== CardType.Creature) return;
     
     try {
-      await onAction(currentPlayerId, { PlayCreature: { card_id: selectedCard.id } });
+      await onAction(currentPlayerId, { PlayCreature: { instance_id: selectedCard.instance_id, column } });
       setSelectedCard(null);
     } catch (error) {
       console.error('Failed to play creature:', error);
     }
   };
 
-  const handlePlayFeign = async () => {
+  const handlePlayFeign = async (column: number) => {
     if (!selectedCard || selectedCard.card_type !== CardType.Feign) return;
     
     try {
-      await onAction(currentPlayerId, { PlayFeign: { card_id: selectedCard.id } });
+      await onAction(currentPlayerId, { PlayFeign: { instance_id: selectedCard.instance_id, column } });
       setSelectedCard(null);
     } catch (error) {
       console.error('Failed to play feign:', error);
@@ -146,33 +72,43 @@ export const GameBoard: React.FC<GameBoardProps> = ({
     if (!selectedCard || selectedCard.card_type !== CardType.Effect) return;
     
     try {
-      await onAction(currentPlayerId, { PlayEffect: { card_id: selectedCard.id } });
+      await onAction(currentPlayerId, { PlayEffect: { instance_id: selectedCard.instance_id } });
       setSelectedCard(null);
     } catch (error) {
       console.error('Failed to play effect:', error);
     }
   };
 
-  const handleAttackWithCreature = async (creatureIndex: number) => {
+  const handleAttackWithCreature = async (column: number) => {
     if (!isCurrentPlayerTurn || gameState.phase !== GamePhase.Attack) return;
     
     try {
-      await onAction(currentPlayerId, { Attack: { creature_index: creatureIndex } });
+      await onAction(currentPlayerId, { Attack: { column } });
     } catch (error) {
       console.error('Failed to attack:', error);
     }
   };
 
-  const handleRevealFeign = async (feignIndex: number) => {
-    if (!isCurrentPlayerTurn) return;
+  const handleRevealFeign = async (column: number) => {
+    if (!isCurrentPlayerTurn && !isRespondingToAttack) return;
     
     try {
-      await onAction(currentPlayerId, { RevealFeign: { feign_index: feignIndex } });
+      await onAction(currentPlayerId, { RevealFeign: { column } });
     } catch (error) {
       console.error('Failed to reveal feign:', error);
     }
   };
 
+  const handlePassResponse = async () => {
+    if (!isRespondingToAttack) return;
+    
+    try {
+      await onAction(currentPlayerId, "PassResponse");
+    } catch (error) {
+      console.error('Failed to pass response:', error);
+    }
+  };
+
   const handleEndPhase = async () => {
     if (!isCurrentPlayerTurn) return;
     
@@ -241,6 +177,11 @@ export const GameBoard: React.FC<GameBoardProps> = ({
               <p className="current-player-name">
                 {gameState.current_player === 1 ? gameState.player1.name : gameState.player2.name}'s Turn
               </p>
+              {isRespondingToAttack && (
+                <p className="draw-phase-hint">
+                  Column {gameState.pending_attack!.column + 1} is under attack - reveal a feign or let it through
+                </p>
+              )}
             </div>
             <div className="button-group">
               {gameState.phase === GamePhase.Draw && isCurrentPlayerTurn && (
@@ -251,10 +192,18 @@ export const GameBoard: React.FC<GameBoardProps> = ({
                   🎴 Draw Card & Continue
                 </button>
               )}
+              {isRespondingToAttack && (
+                <button
+                  onClick={handlePassResponse}
+                  className="end-phase-btn"
+                >
+                  🛡️ Let Attack Through
+                </button>
+              )}
               {gameState.phase !== GamePhase.Draw && (
                 <button
                   onClick={handleEndPhase}
-                  disabled={!isCurrentPlayerTurn}
+                  disabled={!isCurrentPlayerTurn || gameState.pending_attack !== null}
                   className="end-phase-btn"
                 >
                   ➡️ End Phase
@@ -309,7 +258,7 @@ export const GameBoard: React.FC<GameBoardProps> = ({
               </div>
               <div className="stat-item">
                 <div className="stat-badge large hand-badge">
-                  {opponentPlayer.hand.length}
+                  {opponentPlayer.hand_size}
                 </div>
                 <span>Hand</span>
               </div>
@@ -320,28 +269,25 @@ export const GameBoard: React.FC<GameBoardProps> = ({
           <div className="play-area">
             {/* Feigns Row */}
             <div className="feigns-row">
-              {opponentPlayer.board.feigns.map((feign, index) => (
-                <div key={`opp-feign-${index}`} className="feign-card">
-                  <FeignCard feign={feign} />
+              {opponentPlayer.board.feigns.map((feign, column) => (
+                <div key={`opp-feign-${column}`} className="feign-card">
+                  {feign && <FeignCard feign={feign} />}
                 </div>
               ))}
             </div>
             
             {/* Creatures Row */}
             <div className="creatures-row">
-              {opponentPlayer.board.creatures.map((creature, index) => (
-                <div key={`opp-creature-${index}`} className="creature-card">
-                  <CreatureCard 
-                    creature={creature} 
-                    isOpponent={true}
-                  />
+              {opponentPlayer.board.creatures.map((creature, column) => (
+                <div key={`opp-creature-${column}`} className="creature-card">
+                  {creature && (
+                    <CreatureCard 
+                      creature={creature} 
+                      isOpponent={true}
+                    />
+                  )}
                 </div>
               ))}
-              {opponentPlayer.board.creatures.length === 0 && (
-                <div className="no-creatures">
-                  No creatures in play
-                </div>
-              )}
             </div>
           </div>
         </div>
@@ -370,66 +316,58 @@ export const GameBoard: React.FC<GameBoardProps> = ({
           <div className={`play-area current-player-play-area ${selectedCard ? 'has-selected-card' : ''}`}>
             {/* Feigns Row */}
             <div className="feigns-row">
-              {currentPlayer.board.feigns.map((feign, index) => (
-                <div 
-                  key={`curr-feign-${index}`} 
-                  className="feign-card"
-                  onClick={() => handleRevealFeign(index)}
-                >
-                  <FeignCard feign={feign} />
-                </div>
-              ))}
-              {selectedCard?.card_type === CardType.Feign && (
-                <div 
-                  className="add-feign-slot"
-                  onClick={handlePlayFeign}
-                >
-                  <div className="add-feign-icon">
-                    <div className="add-feign-plus">+</div>
-                    <div className="add-feign-text">Play Feign</div>
+              {currentPlayer.board.feigns.map((feign, column) => (
+                feign ? (
+                  <div 
+                    key={`curr-feign-${column}`} 
+                    className="feign-card"
+                    onClick={() => handleRevealFeign(column)}
+                  >
+                    <FeignCard feign={feign} />
                   </div>
-                </div>
-              )}
+                ) : selectedCard?.card_type === CardType.Feign ? (
+                  <div 
+                    key={`curr-feign-${column}`} 
+                    className="add-feign-slot"
+                    onClick={() => handlePlayFeign(column)}
+                  >
+                    <div className="add-feign-icon">
+                      <div className="add-feign-plus">+</div>
+                      <div className="add-feign-text">Play Feign</div>
+                    </div>
+                  </div>
+                ) : (
+                  <div key={`curr-feign-${column}`} className="feign-card" />
+                )
+              ))}
             </div>
             
             {/* Creatures Row */}
             <div className="creatures-row">
-              {currentPlayer.board.creatures.map((creature, index) => (
-                <div key={`curr-creature-${index}`} className="creature-card">
-                  <CreatureCard 
-                    creature={creature} 
-                    isClickable={gameState.phase === GamePhase.Attack && isCurrentPlayerTurn}
-                    onClick={() => handleAttackWithCreature(index)}
-                  />
-                </div>
-              ))}
-              {currentPlayer.board.creatures.length === 0 && (
-                <div className="no-creatures">
-                  {selectedCard?.card_type === CardType.Creature ? (
-                    <div className="play-area-instructions">
-                      <button
-                        onClick={handlePlayCreature}
-                        className="play-creature-btn"
-                      >
-                        Play {selectedCard.name} here
-                      </button>
+              {currentPlayer.board.creatures.map((creature, column) => (
+                creature ? (
+                  <div key={`curr-creature-${column}`} className="creature-card">
+                    <CreatureCard 
+                      creature={creature} 
+                      isClickable={gameState.phase === GamePhase.Attack && isCurrentPlayerTurn}
+                      onClick={() => handleAttackWithCreature(column)}
+                    />
+                  </div>
+                ) : selectedCard?.card_type === CardType.Creature ? (
+                  <div 
+                    key={`curr-creature-${column}`} 
+                    className="add-creature-slot"
+                    onClick={() => handlePlayCreature(column)}
+                  >
+                    <div className="add-creature-icon">
+                      <div className="add-creature-plus">+</div>
+                      <div className="add-creature-text">Play Creature</div>
                     </div>
-                  ) : (
-                    "No creatures in play"
-                  )}
-                </div>
-              )}
-              {selectedCard?.card_type === CardType.Creature && currentPlayer.board.creatures.length > 0 && (
-                <div 
-                  className="add-creature-slot"
-                  onClick={handlePlayCreature}
-                >
-                  <div className="add-creature-icon">
-                    <div className="add-creature-plus">+</div>
-                    <div className="add-creature-text">Play Creature</div>
                   </div>
-                </div>
-              )}
+                ) : (
+                  <div key={`curr-creature-${column}`} className="creature-card" />
+                )
+              ))}
             </div>
           </div>
         </div>
@@ -444,12 +382,12 @@ export const GameBoard: React.FC<GameBoardProps> = ({
             onClick={gameState.phase === GamePhase.Draw && isCurrentPlayerTurn ? handleDrawCard : undefined}
           >
             <div className="deck-pile-cards">
-              {Array.from({ length: Math.min(currentPlayer.deck.length, 8) }, (_, i) => (
+              {Array.from({ length: Math.min(currentPlayer.deck_size, 8) }, (_, i) => (
                 <div key={i} className="deck-card" style={{transform: `translateY(-${i * 2}px) translateX(-${i}px)`}} />
               ))}
             </div>
             <div className="deck-pile-label">
-              <div className="deck-pile-count">{currentPlayer.deck.length}</div>
+              <div className="deck-pile-count">{currentPlayer.deck_size}</div>
               <div className="deck-pile-text">Draw Pile</div>
               {gameState.phase === GamePhase.Draw && isCurrentPlayerTurn && (
                 <div className="deck-pile-hint">Click to draw</div>
@@ -478,11 +416,11 @@ export const GameBoard: React.FC<GameBoardProps> = ({
             </div>
           )}
           <div className="hand-cards-centered">
-            {currentPlayer.hand.map((card, index) => (
+            {currentPlayer.hand.map((card) => (
               <HandCard
-                key={`hand-${card.id}-${index}`}
+                key={`hand-${card.instance_id}`}
                 card={card}
-                isSelected={selectedCard?.id === card.id}
+                isSelected={selectedCard?.instance_id === card.instance_id}
                 isPlayable={canPlayCard(card)}
                 onClick={() => handleCardSelect(card)}
               />
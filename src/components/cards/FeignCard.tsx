@@ -1,9 +1,9 @@
 import React from 'react';
-import type { FeignCard as FeignCardType } from '../../types/game';
+import type { FeignView } from '../../types/game';
 import { Color } from '../../types/game';
 
 interface FeignCardProps {
-  feign: FeignCardType;
+  feign: FeignView;
   onClick?: () => void;
   isClickable?: boolean;
   className?: string;
@@ -32,12 +32,12 @@ export const FeignCard: React.FC<FeignCardProps> = ({
 
   const baseClasses = `
     border-2 rounded-lg p-3 min-h-[100px] w-full max-w-[150px]
-    ${feign.is_revealed ? getColorClass(feign.card.color) : 'border-gray-600 bg-gray-800'}
+    ${feign.is_revealed && feign.card ? getColorClass(feign.card.color) : 'border-gray-600 bg-gray-800'}
     ${isClickable ? 'cursor-pointer hover:shadow-md transition-shadow' : ''}
     ${className}
   `;
 
-  if (!feign.is_revealed) {
+  if (!feign.is_revealed || !feign.card) {
     // Face-down feign card
     return (
       <div
@@ -1,60 +1,170 @@
-import { useState, useCallback, useEffect } from 'react';
+import { useState, useCallback, useEffect, useRef } from 'react';
 import { GameService } from '../services/gameService';
-import type { GameState, PlayerAction, ActionResult } from '../types/game';
+import type { GameId, NewGame, PlayerView, PlayerAction, ActionResultView } from '../types/game';
+
+// The local player in games created here; joined network games seat them
+// wherever the host puts them
+const DEFAULT_VIEWER_ID = 1;
 
 /**
  * Custom hook for managing game state
  */
 export const useGameState = () => {
-  const [gameState, setGameState] = useState<GameState | null>(null);
+  const [gameId, setGameId] = useState<GameId | null>(null);
+  const [gameState, setGameState] = useState<PlayerView | null>(null);
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
+  const [winner, setWinner] = useState<number | null>(null);
+  const [aiThinking, setAiThinking] = useState(false);
+  const [viewerId, setViewerId] = useState(DEFAULT_VIEWER_ID);
+  const [hostingPort, setHostingPort] = useState<number | null>(null); // Waiting for a player to join
+  const [peerDisconnected, setPeerDisconnected] = useState(false);
+
+  // Read by the event handlers, which are only subscribed once
+  const gameIdRef = useRef<GameId | null>(null);
+  const viewerIdRef = useRef(DEFAULT_VIEWER_ID);
+  const hostingRef = useRef(false);
+  useEffect(() => {
+    gameIdRef.current = gameId;
+  }, [gameId]);
 
   /**
-   * Create a new game
+   * Show a game that was just created, loaded, joined or started by a guest
+   */
+  const showGame = useCallback((newGame: NewGame) => {
+    gameIdRef.current = newGame.game_id;
+    viewerIdRef.current = newGame.view.viewer_id;
+    setGameId(newGame.game_id);
+    setViewerId(newGame.view.viewer_id);
+    setGameState(newGame.view);
+    setWinner(null);
+    setAiThinking(false);
+    setPeerDisconnected(false);
+  }, []);
+
+  // The backend pushes every change of every running game, including the
+  // AI's moves; only those of the game shown here are kept
+  useEffect(() => {
+    const isShown = (id: GameId) => id === gameIdRef.current;
+    const subscriptions = [
+      GameService.onStateUpdated(({ game_id, player_id, view }) => {
+        if (isShown(game_id) && player_id === viewerIdRef.current) {
+          setGameState(view);
+        }
+      }),
+      GameService.onGameOver(({ game_id, winner }) => {
+        if (isShown(game_id)) {
+          setWinner(winner);
+        }
+      }),
+      GameService.onAiThinking(({ game_id, thinking }) => {
+        if (isShown(game_id)) {
+          setAiThinking(thinking);
+        }
+      }),
+      GameService.onNetworkGameStarted((newGame) => {
+        if (hostingRef.current) {
+          hostingRef.current = false;
+          setHostingPort(null);
+          showGame(newGame);
+        }
+      }),
+      GameService.onPeerDisconnected(({ game_id, reason }) => {
+        if (isShown(game_id)) {
+          setPeerDisconnected(true);
+          if (reason) {
+            setError(reason);
+          }
+        }
+      }),
+    ];
+    return () => {
+      subscriptions.forEach((subscription) => subscription.then((unlisten) => unlisten()));
+    };
+  }, [showGame]);
+
+  /**
+   * Create a new game against the built-in AI, which plays player 2
    */
   const createGame = useCallback(async (player1Name: string, player2Name: string) => {
     setLoading(true);
     setError(null);
     try {
-      const newGameState = await GameService.createGame(player1Name, player2Name);
-      setGameState(newGameState);
+      showGame(await GameService.createAiGame(player1Name, player2Name));
     } catch (err) {
       setError(err instanceof Error ? err.message : 'Failed to create game');
     } finally {
       setLoading(false);
     }
+  }, [showGame]);
+
+  /**
+   * Host a network game as player 1; it is shown once a player joins
+   */
+  const hostGame = useCallback(async (playerName: string, port?: number) => {
+    setLoading(true);
+    setError(null);
+    try {
+      const hosted = await GameService.hostNetworkGame(playerName, port);
+      hostingRef.current = true;
+      setHostingPort(hosted.port);
+    } catch (err) {
+      setError(err instanceof Error ? err.message : 'Failed to host game');
+    } finally {
+      setLoading(false);
+    }
   }, []);
 
+  /**
+   * Join a network game hosted on another computer
+   */
+  const joinGame = useCallback(async (address: string, playerName: string) => {
+    setLoading(true);
+    setError(null);
+    try {
+      showGame(await GameService.joinNetworkGame(address, playerName));
+    } catch (err) {
+      setError(err instanceof Error ? err.message : 'Failed to join game');
+    } finally {
+      setLoading(false);
+    }
+  }, [showGame]);
+
   /**
    * Refresh game state from backend
    */
   const refreshGameState = useCallback(async () => {
-    if (!gameState) return;
+    if (gameId === null) return;
     
     setLoading(true);
     setError(null);
     try {
-      const updatedState = await GameService.getGameState();
+      const updatedState = await GameService.getGameState(gameId, viewerId);
       setGameState(updatedState);
     } catch (err) {
       setError(err instanceof Error ? err.message : 'Failed to refresh game state');
     } finally {
       setLoading(false);
     }
-  }, [gameState]);
+  }, [gameId, viewerId]);
 
   /**
    * Process a player action
    */
-  const processAction = useCallback(async (playerId: number, action: PlayerAction): Promise<ActionResult> => {
+  const processAction = useCallback(async (playerId: number, action: PlayerAction): Promise<ActionResultView> => {
+    if (gameId === null) {
+      return { success: false, message: 'No game in progress', new_state: null, error: null };
+    }
+
     setLoading(true);
     setError(null);
     try {
-      const result = await GameService.processAction(playerId, action);
+      const result = await GameService.processAction(gameId, playerId, action);
       
-      // Update local state if action was successful and new state is provided
-      if (result.success && result.new_state) {
+      // Update local state if action was successful; the AI's reply arrives
+      // later as state updates, and actions taken for the other player come
+      // back with their view, which the update events replace with ours
+      if (result.success && result.new_state && playerId === viewerId) {
         setGameState(result.new_state);
       }
       
@@ -66,39 +176,51 @@ export const useGameState = () => {
         success: false,
         message: errorMessage,
         new_state: null,
+        error: null,
       };
     } finally {
       setLoading(false);
     }
-  }, []);
+  }, [gameId, viewerId]);
 
   /**
    * Check if game is over
    */
   const checkGameOver = useCallback(async (): Promise<number | null> => {
+    if (gameId === null) return null;
+
     try {
-      return await GameService.checkGameOver();
+      return await GameService.checkGameOver(gameId);
     } catch (err) {
       setError(err instanceof Error ? err.message : 'Failed to check game over');
       return null;
     }
-  }, []);
+  }, [gameId]);
 
   /**
-   * Reset the game
+   * Close the game shown here
    */
   const resetGame = useCallback(async () => {
     setLoading(true);
     setError(null);
     try {
-      await GameService.resetGame();
+      if (gameId !== null) {
+        await GameService.closeGame(gameId);
+      }
+      gameIdRef.current = null;
+      hostingRef.current = false;
+      setGameId(null);
       setGameState(null);
+      setWinner(null);
+      setAiThinking(false);
+      setHostingPort(null);
+      setPeerDisconnected(false);
     } catch (err) {
       setError(err instanceof Error ? err.message : 'Failed to reset game');
     } finally {
       setLoading(false);
     }
-  }, []);
+  }, [gameId]);
 
   /**
    * Get current player
@@ -124,10 +246,18 @@ export const useGameState = () => {
   }, [gameState]);
 
   return {
+    gameId,
     gameState,
     loading,
     error,
+    winner,
+    aiThinking,
+    viewerId,
+    hostingPort,
+    peerDisconnected,
     createGame,
+    hostGame,
+    joinGame,
     refreshGameState,
     processAction,
     checkGameOver,
@@ -1,85 +1,490 @@
 import { invoke } from '@tauri-apps/api/core';
-import type { GameState, PlayerAction, ActionResult } from '../types/game';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import {
+  AI_THINKING_EVENT,
+  GAME_EVENT_EVENT,
+  GAME_OVER_EVENT,
+  NETWORK_GAME_STARTED_EVENT,
+  PEER_DISCONNECTED_EVENT,
+  STATE_UPDATED_EVENT,
+} from '../types/game';
+import type {
+  AiKind,
+  AiThinking,
+  Card,
+  DeckList,
+  DeckRules,
+  ErrorView,
+  GameErrorCode,
+  GameEventNotice,
+  GameId,
+  GameOver,
+  GameSummary,
+  HostedGame,
+  NewGame,
+  PeerDisconnected,
+  PlayerView,
+  PlayerAction,
+  ActionResultView,
+  ReplayFrame,
+  SaveSummary,
+  StateUpdated,
+  UndoAvailability,
+} from '../types/game';
+
+/**
+ * Error thrown when a command fails; keeps the backend's error code so callers
+ * can react to specific failures
+ */
+export class GameServiceError extends Error {
+  code: GameErrorCode | null;
+  details: unknown;
+
+  constructor(context: string, error: unknown) {
+    const view = typeof error === 'object' && error !== null && 'code' in error
+      ? (error as ErrorView)
+      : null;
+    super(`${context}: ${view ? view.message : error}`);
+    this.name = 'GameServiceError';
+    this.code = view ? view.code : null;
+    this.details = view && 'details' in view ? view.details : null;
+  }
+}
 
 /**
  * Service for handling game operations via Tauri commands
  */
 export class GameService {
   /**
-   * Create a new game with two players
+   * Create a new game with two players, returning its id and player 1's view.
+   * Passing a seed makes the deal reproducible.
+   */
+  static async createGame(player1Name: string, player2Name: string, seed?: number): Promise<NewGame> {
+    try {
+      return await invoke<NewGame>('create_game', {
+        player1Name,
+        player2Name,
+        seed: seed ?? null,
+      });
+    } catch (error) {
+      console.error('Failed to create game:', error);
+      throw new GameServiceError('Failed to create game', error);
+    }
+  }
+
+  /**
+   * Create a single-player game where player 2 is controlled by the built-in AI
+   * (the greedy AI unless another is chosen), returning its id and player 1's view
+   */
+  static async createAiGame(
+    player1Name: string,
+    player2Name: string,
+    aiKind?: AiKind,
+    seed?: number,
+  ): Promise<NewGame> {
+    try {
+      return await invoke<NewGame>('create_ai_game', {
+        player1Name,
+        player2Name,
+        aiKind: aiKind ?? null,
+        seed: seed ?? null,
+      });
+    } catch (error) {
+      console.error('Failed to create game:', error);
+      throw new GameServiceError('Failed to create game', error);
+    }
+  }
+
+  /**
+   * Create a new game from each player's deck list, returning its id and player 1's view.
+   * Rejects with the rule violations if either deck is illegal.
    */
-  static async createGame(player1Name: string, player2Name: string): Promise<GameState> {
+  static async createGameWithDecks(
+    player1Name: string,
+    player1Deck: DeckList,
+    player2Name: string,
+    player2Deck: DeckList,
+    rules?: DeckRules,
+    seed?: number,
+  ): Promise<NewGame> {
     try {
-      return await invoke<GameState>('create_game', {
+      return await invoke<NewGame>('create_game_with_decks', {
         player1Name,
+        player1Deck,
         player2Name,
+        player2Deck,
+        rules: rules ?? null,
+        seed: seed ?? null,
       });
     } catch (error) {
       console.error('Failed to create game:', error);
-      throw new Error(`Failed to create game: ${error}`);
+      throw new GameServiceError('Failed to create game', error);
+    }
+  }
+
+  /**
+   * List the running games, oldest first
+   */
+  static async listGames(): Promise<GameSummary[]> {
+    try {
+      return await invoke('list_games');
+    } catch (error) {
+      console.error('Failed to list games:', error);
+      throw new GameServiceError('Failed to list games', error);
+    }
+  }
+
+  /**
+   * Host a network game on the local network as player 1. The game starts,
+   * announced through onNetworkGameStarted, once a player joins from another
+   * computer.
+   */
+  static async hostNetworkGame(playerName: string, port?: number, seed?: number): Promise<HostedGame> {
+    try {
+      return await invoke<HostedGame>('host_network_game', {
+        playerName,
+        port: port ?? null,
+        seed: seed ?? null,
+      });
+    } catch (error) {
+      console.error('Failed to host game:', error);
+      throw new GameServiceError('Failed to host game', error);
+    }
+  }
+
+  /**
+   * Stop waiting for a player to join the network game hosted on port
+   */
+  static async cancelNetworkHost(port: number): Promise<void> {
+    try {
+      await invoke('cancel_network_host', { port });
+    } catch (error) {
+      console.error('Failed to cancel hosting:', error);
+      throw new GameServiceError('Failed to cancel hosting', error);
+    }
+  }
+
+  /**
+   * Join a network game hosted on another computer, given as "host" or
+   * "host:port", returning its id and the local player's view
+   */
+  static async joinNetworkGame(address: string, playerName: string): Promise<NewGame> {
+    try {
+      return await invoke<NewGame>('join_network_game', { address, playerName });
+    } catch (error) {
+      console.error('Failed to join game:', error);
+      throw new GameServiceError('Failed to join game', error);
+    }
+  }
+
+  /**
+   * End a game and forget it. Network games are disconnected.
+   */
+  static async closeGame(gameId: GameId): Promise<void> {
+    try {
+      await invoke('close_game', { gameId });
+    } catch (error) {
+      console.error('Failed to close game:', error);
+      throw new GameServiceError('Failed to close game', error);
     }
   }
 
   /**
-   * Get the current game state
+   * Get the current state of a game as seen by a player
    */
-  static async getGameState(): Promise<GameState> {
+  static async getGameState(gameId: GameId, playerId: number): Promise<PlayerView> {
     try {
-      return await invoke('get_game_state');
+      return await invoke('get_game_state', { gameId, playerId });
     } catch (error) {
       console.error('Failed to get game state:', error);
-      throw new Error(`Failed to get game state: ${error}`);
+      throw new GameServiceError('Failed to get game state', error);
     }
   }
 
   /**
-   * Process a player action
+   * Process a player action, returning the result as seen by that player
    */
-  static async processAction(playerId: number, action: PlayerAction): Promise<ActionResult> {
+  static async processAction(gameId: GameId, playerId: number, action: PlayerAction): Promise<ActionResultView> {
     try {
       return await invoke('process_action', {
+        gameId,
         playerId,
         action,
       });
     } catch (error) {
       console.error('Failed to process action:', error);
-      throw new Error(`Failed to process action: ${error}`);
+      throw new GameServiceError('Failed to process action', error);
+    }
+  }
+
+  /**
+   * Take back a player's last action this turn
+   */
+  static async undoAction(gameId: GameId, playerId: number): Promise<ActionResultView> {
+    try {
+      return await invoke('undo_action', { gameId, playerId });
+    } catch (error) {
+      console.error('Failed to undo action:', error);
+      throw new GameServiceError('Failed to undo action', error);
+    }
+  }
+
+  /**
+   * Repeat a player's last undone action
+   */
+  static async redoAction(gameId: GameId, playerId: number): Promise<ActionResultView> {
+    try {
+      return await invoke('redo_action', { gameId, playerId });
+    } catch (error) {
+      console.error('Failed to redo action:', error);
+      throw new GameServiceError('Failed to redo action', error);
     }
   }
 
   /**
-   * Check if the game is over and who won
+   * Check whether a player can currently undo or redo
    */
-  static async checkGameOver(): Promise<number | null> {
+  static async getUndoAvailability(gameId: GameId, playerId: number): Promise<UndoAvailability> {
     try {
-      return await invoke('check_game_over');
+      return await invoke('get_undo_availability', { gameId, playerId });
+    } catch (error) {
+      console.error('Failed to get undo availability:', error);
+      throw new GameServiceError('Failed to get undo availability', error);
+    }
+  }
+
+  /**
+   * List every action a player may take right now
+   */
+  static async getLegalActions(gameId: GameId, playerId: number): Promise<PlayerAction[]> {
+    try {
+      return await invoke('get_legal_actions', { gameId, playerId });
+    } catch (error) {
+      console.error('Failed to get legal actions:', error);
+      throw new GameServiceError('Failed to get legal actions', error);
+    }
+  }
+
+  /**
+   * Check if a game is over and who won
+   */
+  static async checkGameOver(gameId: GameId): Promise<number | null> {
+    try {
+      return await invoke('check_game_over', { gameId });
     } catch (error) {
       console.error('Failed to check game over:', error);
-      throw new Error(`Failed to check game over: ${error}`);
+      throw new GameServiceError('Failed to check game over', error);
     }
   }
 
   /**
-   * Get the game log
+   * Get the log of a game
    */
-  static async getGameLog(): Promise<string[]> {
+  static async getGameLog(gameId: GameId): Promise<string[]> {
     try {
-      return await invoke('get_game_log');
+      return await invoke('get_game_log', { gameId });
     } catch (error) {
       console.error('Failed to get game log:', error);
-      throw new Error(`Failed to get game log: ${error}`);
+      throw new GameServiceError('Failed to get game log', error);
+    }
+  }
+
+  /**
+   * Get every card definition in the catalog
+   */
+  static async getCardCatalog(): Promise<Card[]> {
+    try {
+      return await invoke('get_card_catalog');
+    } catch (error) {
+      console.error('Failed to get card catalog:', error);
+      throw new GameServiceError('Failed to get card catalog', error);
     }
   }
 
   /**
-   * Reset the game
+   * Render a deck list in the shareable text format
    */
-  static async resetGame(): Promise<string> {
+  static async exportDeckText(deck: DeckList): Promise<string> {
     try {
-      return await invoke('reset_game');
+      return await invoke('export_deck_text', { deck });
     } catch (error) {
-      console.error('Failed to reset game:', error);
-      throw new Error(`Failed to reset game: ${error}`);
+      console.error('Failed to export deck:', error);
+      throw new GameServiceError('Failed to export deck', error);
     }
   }
-} 
\ No newline at end of file
+
+  /**
+   * Encode a deck list as a compact deck code
+   */
+  static async exportDeckCode(deck: DeckList): Promise<string> {
+    try {
+      return await invoke('export_deck_code', { deck });
+    } catch (error) {
+      console.error('Failed to export deck:', error);
+      throw new GameServiceError('Failed to export deck', error);
+    }
+  }
+
+  /**
+   * Parse a deck from either the text format or a deck code
+   */
+  static async importDeck(input: string): Promise<DeckList> {
+    try {
+      return await invoke('import_deck', { input });
+    } catch (error) {
+      console.error('Failed to import deck:', error);
+      throw new GameServiceError('Failed to import deck', error);
+    }
+  }
+
+  /**
+   * Write a finished game's replay to a file
+   */
+  static async saveReplay(gameId: GameId, path: string): Promise<void> {
+    try {
+      await invoke('save_replay', { gameId, path });
+    } catch (error) {
+      console.error('Failed to save replay:', error);
+      throw new GameServiceError('Failed to save replay', error);
+    }
+  }
+
+  /**
+   * Open a replay file for watching, positioned before the first action
+   */
+  static async loadReplay(path: string): Promise<ReplayFrame> {
+    try {
+      return await invoke('load_replay', { path });
+    } catch (error) {
+      console.error('Failed to load replay:', error);
+      throw new GameServiceError('Failed to load replay', error);
+    }
+  }
+
+  /**
+   * Apply the next action of the open replay
+   */
+  static async replayStepForward(): Promise<ReplayFrame> {
+    try {
+      return await invoke('replay_step_forward');
+    } catch (error) {
+      console.error('Failed to step replay:', error);
+      throw new GameServiceError('Failed to step replay', error);
+    }
+  }
+
+  /**
+   * Undo the last applied action of the open replay
+   */
+  static async replayStepBackward(): Promise<ReplayFrame> {
+    try {
+      return await invoke('replay_step_backward');
+    } catch (error) {
+      console.error('Failed to step replay:', error);
+      throw new GameServiceError('Failed to step replay', error);
+    }
+  }
+
+  /**
+   * Jump the open replay to the position after `position` actions
+   */
+  static async replaySeek(position: number): Promise<ReplayFrame> {
+    try {
+      return await invoke('replay_seek', { position });
+    } catch (error) {
+      console.error('Failed to seek replay:', error);
+      throw new GameServiceError('Failed to seek replay', error);
+    }
+  }
+
+  /**
+   * Save a game to the app data directory
+   */
+  static async saveGame(gameId: GameId): Promise<SaveSummary> {
+    try {
+      return await invoke('save_game', { gameId });
+    } catch (error) {
+      console.error('Failed to save game:', error);
+      throw new GameServiceError('Failed to save game', error);
+    }
+  }
+
+  /**
+   * List saved games, newest first
+   */
+  static async listSavedGames(): Promise<SaveSummary[]> {
+    try {
+      return await invoke('list_saved_games');
+    } catch (error) {
+      console.error('Failed to list saved games:', error);
+      throw new GameServiceError('Failed to list saved games', error);
+    }
+  }
+
+  /**
+   * Resume a saved game as a new running game, returning its id and player 1's view
+   */
+  static async loadGame(id: string): Promise<NewGame> {
+    try {
+      return await invoke('load_game', { id });
+    } catch (error) {
+      console.error('Failed to load game:', error);
+      throw new GameServiceError('Failed to load game', error);
+    }
+  }
+
+  /**
+   * Delete a saved game
+   */
+  static async deleteSavedGame(id: string): Promise<void> {
+    try {
+      await invoke('delete_saved_game', { id });
+    } catch (error) {
+      console.error('Failed to delete saved game:', error);
+      throw new GameServiceError('Failed to delete saved game', error);
+    }
+  }
+
+  /**
+   * Subscribe to every change of any running game, including AI moves; the
+   * payload carries the game id and one human seat's view
+   */
+  static onStateUpdated(handler: (update: StateUpdated) => void): Promise<UnlistenFn> {
+    return listen<StateUpdated>(STATE_UPDATED_EVENT, (event) => handler(event.payload));
+  }
+
+  /**
+   * Subscribe to new game events as each human seat sees them
+   */
+  static onGameEvent(handler: (notice: GameEventNotice) => void): Promise<UnlistenFn> {
+    return listen<GameEventNotice>(GAME_EVENT_EVENT, (event) => handler(event.payload));
+  }
+
+  /**
+   * Subscribe to the end of any game
+   */
+  static onGameOver(handler: (gameOver: GameOver) => void): Promise<UnlistenFn> {
+    return listen<GameOver>(GAME_OVER_EVENT, (event) => handler(event.payload));
+  }
+
+  /**
+   * Subscribe to the AI starting and finishing its turn
+   */
+  static onAiThinking(handler: (thinking: AiThinking) => void): Promise<UnlistenFn> {
+    return listen<AiThinking>(AI_THINKING_EVENT, (event) => handler(event.payload));
+  }
+
+  /**
+   * Subscribe to hosted network games starting once a player joins
+   */
+  static onNetworkGameStarted(handler: (game: NewGame) => void): Promise<UnlistenFn> {
+    return listen<NewGame>(NETWORK_GAME_STARTED_EVENT, (event) => handler(event.payload));
+  }
+
+  /**
+   * Subscribe to the other computer in a network game going away
+   */
+  static onPeerDisconnected(handler: (peer: PeerDisconnected) => void): Promise<UnlistenFn> {
+    return listen<PeerDisconnected>(PEER_DISCONNECTED_EVENT, (event) => handler(event.payload));
+  }
+}
@@ -1,5 +1,8 @@
 // TypeScript types that mirror Rust structs for Feign card game
 
+// Number of columns (lanes) on each player's board
+export const BOARD_COLUMNS = 4;
+
 export enum Color {
   Verdant = "Verdant", // Green - Nature, growth, beast synergy
   Cinder = "Cinder",   // Red - Aggression, damage, rage
@@ -15,8 +18,33 @@ export enum CardType {
   Effect = "Effect",
 }
 
+export enum EffectTarget {
+  Owner = "Owner",
+  Opponent = "Opponent",
+  FriendlyCreature = "FriendlyCreature", // The owner's creature in the card's column
+  OpposingCreature = "OpposingCreature", // The opponent's creature in the card's column
+  AllFriendlyCreatures = "AllFriendlyCreatures",
+  AllEnemyCreatures = "AllEnemyCreatures",
+  AllCreatures = "AllCreatures",
+}
+
+export type CardEffect =
+  | { DealDamage: { target: EffectTarget; amount: number } }
+  | { Heal: { target: EffectTarget; amount: number } }
+  | { GrantMana: { amount: number } }
+  | { ModifyStats: { target: EffectTarget; attack: number; defense: number } }
+  | { Shield: { amount: number } }
+  | { SummonToken: { name: string; attack: number; defense: number; duration: number | null } }
+  | { IfGlobalEffectActive: { then: CardEffect[]; otherwise: CardEffect[] } };
+
+export type ContinuousEffect =
+  | { StatModifier: { color: Color | null; attack: number; defense: number } }
+  | { DamageCreaturesEachTurn: { amount: number } }
+  | { ExtraDraw: { count: number } }
+  | { DamageReduction: { amount: number } };
+
 export interface Card {
-  id: number;
+  id: number; // Catalog id shared by every copy of this card
   name: string;
   card_type: CardType;
   color: Color;
@@ -27,28 +55,44 @@ export interface Card {
   defense: number | null;
   // Effect-specific duration (null for non-effects)
   duration: number | null;
+  // Effects executed when the card resolves (e.g. a feign being revealed)
+  effects: CardEffect[];
+  // Modifiers applied while the card is the active global effect
+  continuous_effects: ContinuousEffect[];
+}
+
+// A copy of a card in a game: its definition plus a game-unique instance id
+export interface CardInstance extends Card {
+  instance_id: number;
 }
 
 export interface Creature {
-  card: Card;
+  card: CardInstance;
   current_attack: number;
   current_defense: number;
   is_tapped: boolean;
+  // Turns left before a temporary creature fades; null is permanent
+  remaining_turns: number | null;
+  // Permanent stat changes from card effects, on top of the base card stats
+  attack_modifier: number;
+  defense_modifier: number;
+  // Damage marked on the creature
+  damage: number;
 }
 
 export interface FeignCard {
-  card: Card;
+  card: CardInstance;
   is_revealed: boolean;
 }
 
 export interface GlobalEffect {
-  card: Card;
+  card: CardInstance;
   remaining_duration: number;
 }
 
 export interface PlayerBoard {
-  creatures: Creature[]; // Flexible creature list
-  feigns: FeignCard[];   // Flexible feign list
+  creatures: (Creature | null)[]; // Front row, one creature per column
+  feigns: (FeignCard | null)[];   // Back row, one feign per column
 }
 
 export interface Player {
@@ -56,8 +100,9 @@ export interface Player {
   name: string;
   life: number;
   mana: number;
-  hand: Card[];
-  deck: Card[];
+  shield: number; // Damage prevented before life is lost
+  hand: CardInstance[];
+  deck: CardInstance[];
   board: PlayerBoard;
 }
 
@@ -75,19 +120,288 @@ export interface GameState {
   turn_number: number;
   phase: GamePhase;
   global_effect: GlobalEffect | null;
-  game_log: string[];
+  // Attack waiting for the defending player to respond
+  pending_attack: PendingAttack | null;
+  // Seed of the game's random stream; never included in player views
+  seed: number;
+  events: GameEvent[]; // Everything that happened so far, in order
+}
+
+export interface PendingAttack {
+  attacking_player: number;
+  column: number;
 }
 
+// Who an effect or attack landed on
+export type EventTarget =
+  | { Player: { player_id: number; life: number } } // Life after the event
+  | { Creature: { player_id: number; instance_id: number; name: string } };
+
+// Something that happened in a game; the text log is rendered from these
+export type GameEvent =
+  | "GameStarted"
+  | { TurnStarted: { turn: number; player_id: number } }
+  | { PhaseChanged: { phase: GamePhase } }
+  | { CardDrawn: { player_id: number; instance_id: number | null } } // null when drawn by the opponent
+  | { DeckEmpty: { player_id: number } }
+  | { CreaturePlayed: { player_id: number; card: CardInstance; column: number } }
+  | { FeignPlayed: { player_id: number; card: CardInstance | null; column: number } } // null unless owned
+  | { EffectPlayed: { player_id: number; card: CardInstance } }
+  | { EffectReplaced: { name: string } }
+  | { EffectExpired: { name: string } }
+  | { AttackDeclared: { player_id: number; column: number } }
+  | { ResponsePassed: { player_id: number } }
+  | { AttackStopped: { player_id: number; column: number } }
+  | { CreatureAttacked: { player_id: number; column: number; name: string; attack: number } }
+  | { CreatureBlocked: { player_id: number; column: number; name: string; defense: number } }
+  | { FeignRevealed: { player_id: number; card: CardInstance; column: number } }
+  | { DamageDealt: { source: string; target: EventTarget; amount: number } }
+  | { Healed: { target: EventTarget; amount: number } }
+  | { ManaGained: { player_id: number; amount: number } }
+  | { StatsModified: { player_id: number; instance_id: number; name: string; attack: number; defense: number } }
+  | { ShieldRaised: { player_id: number; shield: number } } // Total shield after the event
+  | { TokenSummoned: { player_id: number; name: string; attack: number; defense: number; column: number } }
+  | { NoRoomForToken: { player_id: number; name: string } }
+  | { CreatureSurvived: { player_id: number; instance_id: number; name: string; defense: number } }
+  | { CreatureDestroyed: { player_id: number; instance_id: number; name: string } }
+  | { CreatureFaded: { player_id: number; instance_id: number; name: string } };
+
 export type PlayerAction = 
-  | { PlayCreature: { card_id: number } }
-  | { PlayFeign: { card_id: number } }
-  | { PlayEffect: { card_id: number } }
-  | { Attack: { creature_index: number } }
-  | { RevealFeign: { feign_index: number } }
+  | { PlayCreature: { instance_id: number; column: number } }
+  | { PlayFeign: { instance_id: number; column: number } }
+  | { PlayEffect: { instance_id: number } }
+  | { Attack: { column: number } }
+  | { RevealFeign: { column: number } }
+  // Decline to respond to a pending attack
+  | "PassResponse"
   | "EndPhase";
 
-export interface ActionResult {
+// A feign slot as seen by one player; face-down cards hide their identity
+// from everyone but their owner
+export interface FeignView {
+  card: CardInstance | null; // null when the viewer only sees the card back
+  is_revealed: boolean;
+}
+
+export interface BoardView {
+  creatures: (Creature | null)[];
+  feigns: (FeignView | null)[];
+}
+
+// A player as seen by one player: hidden zones are reduced to counts
+export interface PlayerInfoView {
+  id: number;
+  name: string;
+  life: number;
+  mana: number;
+  shield: number;
+  hand: CardInstance[]; // Empty unless the viewer owns this hand
+  hand_size: number;
+  deck_size: number;
+  board: BoardView;
+}
+
+// The game state projected for a single player, with hidden information removed
+export interface PlayerView {
+  viewer_id: number;
+  player1: PlayerInfoView;
+  player2: PlayerInfoView;
+  current_player: number;
+  turn_number: number;
+  phase: GamePhase;
+  global_effect: GlobalEffect | null;
+  pending_attack: PendingAttack | null;
+  events: GameEvent[];
+  game_log: string[]; // `events` rendered as text
+}
+
+export interface ActionResultView {
   success: boolean;
   message: string;
-  new_state: GameState | null;
-} 
\ No newline at end of file
+  new_state: PlayerView | null;
+  error: ErrorView | null; // Set when the action was rejected
+}
+
+// Whether a player can currently undo or redo
+export interface UndoAvailability {
+  can_undo: boolean;
+  can_redo: boolean;
+}
+
+// Result of an accepted action
+export interface ActionResult {
+  message: string;
+  new_state: GameState;
+}
+
+// Why an action or command was rejected; `details` carries the variant's fields
+export type GameError =
+  | { code: "NotYourTurn" }
+  | { code: "WrongPhase"; details: { expected: GamePhase; actual: GamePhase } }
+  | { code: "CardNotInHand"; details: { instance_id: number } }
+  | { code: "NotEnoughMana"; details: { required: number; available: number } }
+  | { code: "WrongCardType"; details: { expected: CardType; actual: CardType } }
+  | { code: "InvalidIndex"; details: { index: number; max: number } }
+  | { code: "ColumnOccupied"; details: { column: number } }
+  | { code: "NoCreature"; details: { column: number } }
+  | { code: "CreatureAlreadyAttacked"; details: { column: number } }
+  | { code: "NoFeign"; details: { column: number } }
+  | { code: "FeignAlreadyRevealed"; details: { column: number } }
+  | { code: "NoPendingAttack" }
+  | { code: "AwaitingResponse" }
+  | { code: "InvalidResponse" }
+  | { code: "GameOver"; details: { winner: number } }
+  | { code: "NothingToUndo" }
+  | { code: "NothingToRedo" }
+  | { code: "GameNotFound"; details: { game_id: number } }
+  | { code: "AiControlled"; details: { player_id: number } }
+  | { code: "RemoteControlled"; details: { player_id: number } }
+  | { code: "HostedRemotely"; details: { game_id: number } }
+  | { code: "NotHosting"; details: { port: number } }
+  | { code: "InvalidDeck"; details: { player_name: string; violations: string[] } }
+  | { code: "InvalidDeckImport"; details: { problems: string[] } }
+  | { code: "GameNotRecorded" }
+  | { code: "GameInProgress"; details: { game_id: number } }
+  | { code: "NoReplayLoaded" }
+  | { code: "ReplayDiverged"; details: { message: string } }
+  | { code: "InvalidSaveId"; details: { id: string } }
+  | { code: "NetworkGameNotSaved"; details: { game_id: number } }
+  | { code: "InvalidFile"; details: { path: string; message: string } }
+  | { code: "Io"; details: { path: string; message: string } }
+  | { code: "Network"; details: NetError }
+  | { code: "Internal"; details: { message: string } };
+
+// Why a network connection failed, given as the details of a "Network" error
+export type NetError =
+  | { code: "Io"; details: { message: string } }
+  | { code: "Protocol"; details: { message: string } }
+  | { code: "VersionMismatch"; details: { ours: number; theirs: number } }
+  | { code: "Rejected"; details: { reason: string } }
+  | { code: "CommitmentMismatch"; details: { column: number } }
+  | { code: "CardMismatch"; details: { instance_id: number } }
+  | { code: "TimedOut" }
+  | { code: "Cancelled" }
+  | { code: "Disconnected" };
+
+export type GameErrorCode = GameError["code"];
+
+// An error as sent by the backend: its code and details plus a readable message
+export type ErrorView = GameError & { message: string };
+
+// A deck described by catalog ids and copy counts
+export interface DeckEntry {
+  card_id: number;
+  count: number;
+}
+
+export interface DeckList {
+  entries: DeckEntry[];
+}
+
+// Construction rules a deck must satisfy to be played
+export interface DeckRules {
+  min_size: number;
+  max_size: number;
+  max_copies: number; // Copies allowed of any single card
+  max_colors: number | null; // Distinct colors allowed; null is unlimited
+}
+
+// The built-in computer opponents
+export type AiKind = 'Greedy' | 'Mcts';
+
+// One recorded action of a replay and when it was taken
+export interface ReplayAction {
+  player_id: number;
+  turn: number;
+  phase: GamePhase;
+  action: PlayerAction;
+}
+
+// A position in a replay being watched; replays show the full state
+export interface ReplayFrame {
+  position: number; // Actions applied so far
+  length: number; // Total actions in the replay
+  last_action: ReplayAction | null;
+  state: GameState;
+}
+
+// Summary of a saved game
+export interface SaveMetadata {
+  player1_name: string;
+  player2_name: string;
+  turn_number: number;
+  saved_at: number; // Seconds since the Unix epoch
+}
+
+export interface SaveSummary {
+  id: string;
+  metadata: SaveMetadata;
+}
+
+// A running game, identified by the id the backend assigned it
+export type GameId = number;
+
+// A game just created, loaded or joined, with the local player's view of it
+// (player 1's unless the game was joined)
+export interface NewGame {
+  game_id: GameId;
+  view: PlayerView;
+}
+
+// A running game as listed to the player
+export interface GameSummary {
+  game_id: GameId;
+  player1_name: string;
+  player2_name: string;
+  turn_number: number;
+  ai: { player_id: number; kind: AiKind } | null; // The AI seated in the game, if any
+  winner: number | null;
+  joined: boolean; // Hosted on another computer
+}
+
+// A network game waiting for a player to join
+export interface HostedGame {
+  port: number;
+}
+
+// Payloads of the events the backend pushes whenever a game changes; each
+// names the game it belongs to
+export const STATE_UPDATED_EVENT = 'game://state-updated';
+export const GAME_EVENT_EVENT = 'game://event';
+export const GAME_OVER_EVENT = 'game://game-over';
+export const AI_THINKING_EVENT = 'game://ai-thinking';
+export const NETWORK_GAME_STARTED_EVENT = 'game://network-game-started'; // Payload is a NewGame
+export const PEER_DISCONNECTED_EVENT = 'game://peer-disconnected';
+
+// A human seat's view after a change
+export interface StateUpdated {
+  game_id: GameId;
+  player_id: number;
+  view: PlayerView;
+}
+
+// A new game event as one seat sees it
+export interface GameEventNotice {
+  game_id: GameId;
+  player_id: number;
+  index: number; // Position of the event in the game's event list
+  event: GameEvent;
+}
+
+export interface GameOver {
+  game_id: GameId;
+  winner: number;
+}
+
+// The AI started or stopped working on its turn
+export interface AiThinking {
+  game_id: GameId;
+  player_id: number;
+  thinking: boolean;
+}
+
+// The other computer in a network game went away
+export interface PeerDisconnected {
+  game_id: GameId;
+  reason: string | null; // Set when the connection failed, e.g. the host broke a feign commitment
+}